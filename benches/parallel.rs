@@ -0,0 +1,58 @@
+//! Compares `stooge_sort_by` with `stooge_sort_par_by`.
+//!
+//! Run with `cargo bench --features parallel`.
+
+use std::cmp::Ordering;
+use std::sync::atomic::{self, AtomicU64};
+use std::time::{Duration, Instant};
+use stoogesort::{Stooge, StoogePar};
+
+static SINK: AtomicU64 = AtomicU64::new(0);
+
+/// Compares `a` and `b` after wasting roughly `work` iterations' worth of time, to
+/// stand in for an expensive comparator.
+fn slow_cmp(a: &u64, b: &u64, work: u32) -> Ordering {
+    let mut x = *a ^ *b;
+    for _ in 0..work {
+        x = x
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407)
+            .rotate_left(17);
+    }
+    // Keep the loop from being optimized away.
+    SINK.fetch_xor(x, atomic::Ordering::Relaxed);
+    a.cmp(b)
+}
+
+fn time<F: FnOnce()>(f: F) -> Duration {
+    let start = Instant::now();
+    f();
+    start.elapsed()
+}
+
+// The `parallel` feature needs Rust 1.63 anyway.
+#[clippy::msrv = "1.63"]
+fn main() {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    println!("{} thread(s) available", threads);
+
+    for &(len, work) in &[(200, 0), (200, 1000), (400, 100)] {
+        let input: Vec<u64> = (0..len).map(|n| (n * 7919) % len).collect();
+
+        let mut serial = input.clone();
+        let serial_time = time(|| serial.stooge_sort_by(|a, b| slow_cmp(a, b, work)));
+
+        let mut parallel = input.clone();
+        let parallel_time = time(|| parallel.stooge_sort_par_by(|a, b| slow_cmp(a, b, work)));
+
+        assert_eq!(serial, parallel);
+        println!(
+            "n = {:4}, work = {:5}: serial {:9.3?}, parallel {:9.3?}, speedup {:.2}x",
+            len,
+            work,
+            serial_time,
+            parallel_time,
+            serial_time.as_secs_f64() / parallel_time.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Sorting anything that can compare and swap by index.
+
+use crate::WorkStack;
+
+/// Indexed access to a sequence, for sorting things that aren't slices.
+///
+/// Stooge sort only ever compares two elements and maybe swaps them, so it can sort
+/// anything that can do those by index: ring buffers, ropes, memory-mapped tables, and
+/// so on. This is the same idea as Go's `sort.Interface`.
+///
+/// Pass an implementation to [`stooge_sort_access`].
+///
+/// ```
+/// use stoogesort::{stooge_sort_access, StoogeAccess};
+///
+/// /// Every other element of a slice.
+/// struct Evens<'a>(&'a mut [i32]);
+///
+/// impl StoogeAccess for Evens<'_> {
+///     fn len(&self) -> usize {
+///         (self.0.len() + 1) / 2
+///     }
+///     fn less(&mut self, i: usize, j: usize) -> bool {
+///         self.0[2 * i] < self.0[2 * j]
+///     }
+///     fn swap(&mut self, i: usize, j: usize) {
+///         self.0.swap(2 * i, 2 * j);
+///     }
+/// }
+///
+/// let mut v = [5, 0, 3, 0, 4, 0, 1];
+/// stooge_sort_access(&mut Evens(&mut v));
+/// assert_eq!(v, [1, 0, 3, 0, 4, 0, 5]);
+/// ```
+pub trait StoogeAccess {
+    /// Returns the number of elements.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if there are no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if element `i` should sort before element `j`.
+    ///
+    /// This must define a strict total ordering for the result to be sorted. It takes
+    /// `&mut self` so that implementations can move cursors, fill caches, and the like.
+    fn less(&mut self, i: usize, j: usize) -> bool;
+
+    /// Swaps elements `i` and `j`.
+    fn swap(&mut self, i: usize, j: usize);
+}
+
+impl<T: Ord> StoogeAccess for [T] {
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+    fn less(&mut self, i: usize, j: usize) -> bool {
+        self[i] < self[j]
+    }
+    fn swap(&mut self, i: usize, j: usize) {
+        <[T]>::swap(self, i, j);
+    }
+}
+
+impl<A: StoogeAccess + ?Sized> StoogeAccess for &mut A {
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+    fn less(&mut self, i: usize, j: usize) -> bool {
+        (**self).less(i, j)
+    }
+    fn swap(&mut self, i: usize, j: usize) {
+        (**self).swap(i, j);
+    }
+}
+
+/// Sorts `data` using stooge sort.
+///
+/// This makes exactly the same comparisons and swaps as
+/// [`stooge_sort`](crate::Stooge::stooge_sort) would on a slice of the same elements.
+///
+/// This sort is unstable, has worst-case
+/// time complexity of O(n^(log(3)/log(1.5)))
+/// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+pub fn stooge_sort_access<A: StoogeAccess + ?Sized>(data: &mut A) {
+    if data.len() < 2 {
+        return;
+    }
+
+    let mut work = WorkStack::new(0, data.len() - 1);
+
+    while let Some((left, right)) = work.next(&mut ()) {
+        if !data.less(left, right) {
+            data.swap(left, right);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Stooge;
+
+    /// Keys in one slice and their payloads in another, sorted by key.
+    struct Split<'a> {
+        keys: &'a mut [u8],
+        payloads: &'a mut [usize],
+        log: Vec<(usize, usize)>,
+    }
+
+    impl StoogeAccess for Split<'_> {
+        fn len(&self) -> usize {
+            self.keys.len()
+        }
+        fn less(&mut self, i: usize, j: usize) -> bool {
+            self.log.push((i, j));
+            self.keys[i] < self.keys[j]
+        }
+        fn swap(&mut self, i: usize, j: usize) {
+            self.keys.swap(i, j);
+            self.payloads.swap(i, j);
+        }
+    }
+
+    #[test]
+    fn matches_slice_sort() {
+        crate::test_util::assert_sorts_like_stooge_sort(80, |pairs| {
+            let (mut keys, mut payloads): (Vec<u8>, Vec<usize>) = pairs.into_iter().unzip();
+            let stats = keys.clone().stooge_sort_with_stats();
+
+            let mut split = Split {
+                keys: &mut keys,
+                payloads: &mut payloads,
+                log: Vec::new(),
+            };
+            stooge_sort_access(&mut split);
+            assert_eq!(split.log.len() as u64, stats.comparisons);
+
+            keys.into_iter().zip(payloads).collect()
+        });
+    }
+
+    #[test]
+    fn slices() {
+        let mut v = [3, 1, 2, 0];
+        stooge_sort_access(&mut v[..]);
+        assert_eq!(v, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn short() {
+        let mut empty: [u8; 0] = [];
+        stooge_sort_access(&mut empty[..]);
+
+        let mut split = Split {
+            keys: &mut [1],
+            payloads: &mut [0],
+            log: Vec::new(),
+        };
+        stooge_sort_access(&mut split);
+        assert!(split.log.is_empty());
+    }
+}
@@ -0,0 +1,239 @@
+//! Sorts that need to allocate.
+
+use crate::permutation::apply_indices;
+use crate::{sealed, stooge_sort, Stooge};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// Stooge sorts for slices that allocate O(n) extra space, which need the `alloc`
+/// feature.
+///
+/// Like [`Stooge`], this trait is sealed: it's only implemented for `[T]`.
+pub trait StoogeAlloc<T>: sealed::Sealed {
+    /// Sorts the slice using stooge sort with a key extraction function,
+    /// calling the key function only once per element.
+    ///
+    /// This sort is unstable, has worst-case
+    /// time complexity of O(m * n + n^(log(3)/log(1.5)))
+    /// ≈ O(m * n + n^2.7095), where the key function is O(m),
+    /// and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// For expensive key functions (e.g. functions that are not simple property accesses or
+    /// basic operations), this is likely to be significantly faster than
+    /// [`stooge_sort_by_key`](Stooge::stooge_sort_by_key), which calls the key function
+    /// twice per comparison.
+    ///
+    /// The keys are computed up front and stooge-sorted alongside the indices of their
+    /// elements; the resulting permutation is then applied to the slice. This allocates
+    /// O(n) extra space, so it requires the `alloc` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stoogesort::StoogeAlloc;
+    /// let mut v = [-5i32, 4, 32, -3, 2];
+    ///
+    /// v.stooge_sort_by_cached_key(|k| k.to_string());
+    /// assert!(v == [-3, -5, 2, 32, 4]);
+    /// ```
+    fn stooge_sort_by_cached_key<F, K>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord;
+    /// Sorts the slice using stooge sort, preserving the initial order of equal elements.
+    ///
+    /// This sort is stable (i.e., does not reorder equal elements), has worst-case
+    /// time complexity of O(n^(log(3)/log(1.5)))
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// Stooge sort swaps elements across the whole range it's sorting, so it can't be
+    /// made stable just by refusing to swap equal elements. Instead, the indices of the
+    /// elements are sorted, with ties broken by original position, and the resulting
+    /// permutation is applied to the slice. This allocates O(n) extra space, so it
+    /// requires the `alloc` feature (as do the other stable sorts).
+    ///
+    /// ```
+    /// use stoogesort::StoogeAlloc;
+    /// let mut v = [-5, 4, 1, -3, 2];
+    ///
+    /// v.stooge_sort_stable();
+    /// assert!(v == [-5, -3, 1, 2, 4]);
+    /// ```
+    fn stooge_sort_stable(&mut self)
+    where
+        T: Ord;
+    /// Sorts the slice using stooge sort with a comparator function, preserving the
+    /// initial order of equal elements.
+    ///
+    /// This sort is stable (i.e., does not reorder equal elements), has worst-case
+    /// time complexity of O(n^(log(3)/log(1.5)))
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// The comparator function must define a total ordering for the elements in the slice,
+    /// as with [`stooge_sort_by`](Stooge::stooge_sort_by).
+    ///
+    /// ```
+    /// use stoogesort::StoogeAlloc;
+    /// let mut v = [(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')];
+    ///
+    /// v.stooge_sort_stable_by(|a, b| a.0.cmp(&b.0));
+    /// assert_eq!(v, [(0, 'b'), (0, 'd'), (1, 'a'), (1, 'c')]);
+    /// ```
+    fn stooge_sort_stable_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering;
+    /// Sorts the slice using stooge sort with a key extraction function, preserving the
+    /// initial order of equal elements.
+    ///
+    /// This sort is stable (i.e., does not reorder equal elements), has worst-case
+    /// time complexity of O(n^(log(3)/log(1.5)) * m)
+    /// ≈ O(n^2.7095 * m), where the key function is O(m),
+    /// and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stoogesort::StoogeAlloc;
+    /// let mut v = [-5i32, 4, 1, -3, 5, 3];
+    ///
+    /// v.stooge_sort_stable_by_key(|k| k.abs());
+    /// assert!(v == [1, -3, 3, 4, -5, 5]);
+    /// ```
+    fn stooge_sort_stable_by_key<F, K>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord;
+    /// Returns the permutation that would stooge sort the slice, without moving anything.
+    ///
+    /// The `i`th index in the result is the index of the element that
+    /// [`stooge_sort`](Stooge::stooge_sort) would put at position `i`. Pass it to
+    /// [`apply_permutation`](crate::apply_permutation) to actually sort the slice (and any others it's
+    /// parallel to).
+    ///
+    /// This has worst-case time complexity of O(n^(log(3)/log(1.5)))
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+    /// It allocates the O(n) result, so it requires the `alloc` feature.
+    ///
+    /// ```
+    /// use stoogesort::StoogeAlloc;
+    /// let v = [-5, 4, 1, -3, 2];
+    ///
+    /// assert_eq!(v.stooge_argsort(), [0, 3, 2, 4, 1]);
+    /// ```
+    fn stooge_argsort(&self) -> Vec<usize>
+    where
+        T: Ord;
+    /// Returns the permutation that would stooge sort the slice with a comparator
+    /// function, without moving anything.
+    ///
+    /// The `i`th index in the result is the index of the element that
+    /// [`stooge_sort_by`](Stooge::stooge_sort_by) would put at position `i`.
+    ///
+    /// This has worst-case time complexity of O(n^(log(3)/log(1.5)))
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+    /// It allocates the O(n) result, so it requires the `alloc` feature.
+    ///
+    /// ```
+    /// use stoogesort::StoogeAlloc;
+    /// let floats = [5f64, 4.0, 1.0, 3.0, 2.0];
+    /// let order = floats.stooge_argsort_by(|a, b| a.partial_cmp(b).unwrap());
+    /// assert_eq!(order, [2, 4, 3, 1, 0]);
+    /// ```
+    fn stooge_argsort_by<F>(&self, compare: F) -> Vec<usize>
+    where
+        F: FnMut(&T, &T) -> Ordering;
+    /// Returns the permutation that would stooge sort the slice with a key extraction
+    /// function, without moving anything.
+    ///
+    /// The `i`th index in the result is the index of the element that
+    /// [`stooge_sort_by_key`](Stooge::stooge_sort_by_key) would put at position `i`.
+    ///
+    /// This has worst-case time complexity of O(n^(log(3)/log(1.5)) * m)
+    /// ≈ O(n^2.7095 * m), where the key function is O(m),
+    /// and doesn't recurse (see [Recursion](crate#recursion)).
+    /// It allocates the O(n) result, so it requires the `alloc` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stoogesort::{apply_permutation, StoogeAlloc};
+    /// let mut names = ["carol", "alice", "bob"];
+    /// let mut ages = [35, 30, 25];
+    ///
+    /// let order = ages.stooge_argsort_by_key(|&age| age);
+    /// apply_permutation(&mut names, &order);
+    /// apply_permutation(&mut ages, &order);
+    /// assert_eq!(names, ["bob", "alice", "carol"]);
+    /// assert_eq!(ages, [25, 30, 35]);
+    /// ```
+    fn stooge_argsort_by_key<F, K>(&self, f: F) -> Vec<usize>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord;
+}
+
+impl<T> StoogeAlloc<T> for [T] {
+    fn stooge_sort_by_cached_key<F, K>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        if self.is_empty() || self.len() == 1 {
+        } else {
+            let keys: Vec<K> = self.iter().map(f).collect();
+            let mut indices: Vec<usize> = (0..self.len()).collect();
+            stooge_sort(&mut indices, 0, self.len() - 1, &mut |&a, &b| {
+                keys[a].lt(&keys[b])
+            });
+            apply_indices(self, &mut indices);
+        }
+    }
+    fn stooge_sort_stable(&mut self)
+    where
+        T: Ord,
+    {
+        self.stooge_sort_stable_by(T::cmp);
+    }
+    fn stooge_sort_stable_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.is_empty() || self.len() == 1 {
+        } else {
+            let mut indices: Vec<usize> = (0..self.len()).collect();
+            let v: &[T] = self;
+            stooge_sort(&mut indices, 0, v.len() - 1, &mut |&a, &b| {
+                compare(&v[a], &v[b]).then(a.cmp(&b)) == Ordering::Less
+            });
+            apply_indices(self, &mut indices);
+        }
+    }
+    fn stooge_sort_stable_by_key<F, K>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.stooge_sort_stable_by(|a, b| f(a).cmp(&f(b)));
+    }
+    fn stooge_argsort(&self) -> Vec<usize>
+    where
+        T: Ord,
+    {
+        self.stooge_argsort_by(T::cmp)
+    }
+    fn stooge_argsort_by<F>(&self, mut compare: F) -> Vec<usize>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut indices: Vec<usize> = (0..self.len()).collect();
+        indices.stooge_sort_by(|&a, &b| compare(&self[a], &self[b]));
+        indices
+    }
+    fn stooge_argsort_by_key<F, K>(&self, mut f: F) -> Vec<usize>
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.stooge_argsort_by(|a, b| f(a).cmp(&f(b)))
+    }
+}
@@ -1,20 +1,408 @@
+use std::env;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
 use std::io;
-use std::io::BufRead;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::num::{IntErrorKind, ParseIntError};
+use std::path::{Path, PathBuf};
+use std::process;
 use stoogesort::Stooge;
-fn main() {
-    if atty::is(atty::Stream::Stdin) {
-        println!("Pipe in a newline-separated list of ints");
-        return;
+
+const USAGE: &str = "\
+Usage: stoogesort [OPTION]... [FILE]...
+
+Sorts lines from the FILEs, all together, using stooge sort. With no FILE, or
+when FILE is -, reads stdin.
+
+Options:
+  -n, --numeric        sort lines as integers
+  -g, --float          sort lines as floating-point numbers
+      --bytes          sort lines byte by byte, even if they aren't UTF-8
+      --skip-invalid   report lines that can't be parsed and leave them out,
+                       rather than stopping
+  -o, --output FILE    write to FILE instead of stdout; FILE can be one of the
+                       inputs, and is only replaced once the sort succeeds
+  -h, --help           print this message
+
+Exit status is 0 on success, 64 for a bad option, 65 for a line that can't
+be parsed and 74 for an I/O error.";
+
+/// How to compare lines.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// As strings.
+    Lexicographic,
+    /// As `i64`s.
+    Numeric,
+    /// As `f64`s, in IEEE 754 totalOrder.
+    Float,
+    /// As raw bytes.
+    Bytes,
+}
+
+struct Options {
+    mode: Mode,
+    skip_invalid: bool,
+    /// Files to read, where `-` is stdin.
+    inputs: Vec<String>,
+    output: Option<PathBuf>,
+}
+
+/// Everything that can stop the sort, each with its own exit code.
+enum Error {
+    /// A bad command line.
+    Usage(String),
+    /// A line that can't be parsed in the chosen mode (line numbers start at 1).
+    Parse {
+        file: Option<String>,
+        line: usize,
+        message: String,
+    },
+    /// Failing to read or write `file` (or stdin or stdout, if there's no file).
+    Io {
+        file: Option<String>,
+        error: io::Error,
+    },
+}
+
+impl Error {
+    /// Exit codes, from BSD's sysexits.h.
+    fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => 64,
+            Error::Parse { .. } => 65,
+            Error::Io { .. } => 74,
+        }
+    }
+
+    /// Returns a function that wraps an `io::Error` from reading or writing `file`.
+    fn io(file: &Path) -> impl FnOnce(io::Error) -> Error {
+        let file = file.display().to_string();
+        move |error| Error::Io {
+            file: Some(file),
+            error,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Usage(message) => write!(f, "{}", message),
+            Error::Parse {
+                file,
+                line,
+                message,
+            } => {
+                if let Some(file) = file {
+                    write!(f, "{}: ", file)?;
+                }
+                write!(f, "line {}: {}", line, message)
+            }
+            Error::Io { file, error } => {
+                if let Some(file) = file {
+                    write!(f, "{}: ", file)?;
+                }
+                write!(f, "{}", error)
+            }
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io { file: None, error }
+    }
+}
+
+fn parse_args() -> Result<Options, Error> {
+    let mut options = Options {
+        mode: Mode::Lexicographic,
+        skip_invalid: false,
+        inputs: Vec::new(),
+        output: None,
+    };
+    let mut args = env::args().skip(1);
+    let mut only_files = false;
+    while let Some(arg) = args.next() {
+        if only_files || arg == "-" || !arg.starts_with('-') {
+            options.inputs.push(arg);
+            continue;
+        }
+        let mode = match arg.as_str() {
+            "-n" | "--numeric" => Mode::Numeric,
+            "-g" | "--float" => Mode::Float,
+            "--bytes" => Mode::Bytes,
+            "--skip-invalid" => {
+                options.skip_invalid = true;
+                continue;
+            }
+            "-o" | "--output" => {
+                let file = args
+                    .next()
+                    .ok_or_else(|| Error::Usage(format!("option '{}' needs a file", arg)))?;
+                options.output = Some(file.into());
+                continue;
+            }
+            "--" => {
+                only_files = true;
+                continue;
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            _ => match arg.strip_prefix("--output=") {
+                Some(file) => {
+                    options.output = Some(file.into());
+                    continue;
+                }
+                None => return Err(Error::Usage(format!("unrecognized option '{}'", arg))),
+            },
+        };
+        if options.mode != Mode::Lexicographic && options.mode != mode {
+            return Err(Error::Usage(
+                "only one of --numeric, --float and --bytes can be given".to_string(),
+            ));
+        }
+        options.mode = mode;
+    }
+    if options.inputs.is_empty() {
+        options.inputs.push("-".to_string());
+    }
+    Ok(options)
+}
+
+/// Reads every line of every input and parses it with `parse`, which returns a message
+/// saying what's wrong with the line if it can't be parsed.
+///
+/// Unparseable lines are an error, unless `skip_invalid` is set, in which case they're
+/// reported and left out.
+fn read_inputs<T, F>(inputs: &[String], skip_invalid: bool, mut parse: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(Vec<u8>) -> Result<T, String>,
+{
+    let stdin = io::stdin();
+    let mut parsed = Vec::new();
+    for input in inputs {
+        let (file, reader): (Option<&String>, Box<dyn BufRead + '_>) = if input == "-" {
+            (None, Box::new(stdin.lock()))
+        } else {
+            let f = File::open(input).map_err(Error::io(input.as_ref()))?;
+            (Some(input), Box::new(BufReader::new(f)))
+        };
+
+        for (i, line) in reader.split(b'\n').enumerate() {
+            let line = line.map_err(|error| Error::Io {
+                file: file.cloned(),
+                error,
+            })?;
+            match parse(line) {
+                Ok(x) => parsed.push(x),
+                Err(message) => {
+                    let e = Error::Parse {
+                        file: file.cloned(),
+                        line: i + 1,
+                        message,
+                    };
+                    if !skip_invalid {
+                        return Err(e);
+                    }
+                    eprintln!("stoogesort: {}", e);
+                }
+            }
+        }
+    }
+    Ok(parsed)
+}
+
+/// Writes the sorted lines with `write`, to `output` if there is one or stdout if not.
+///
+/// Like `sort -o`, `output` can be one of the inputs, since they've all been read by
+/// now. A regular file is only replaced once everything has been written, by renaming
+/// a temporary file next to it over it. If `output` is a symlink, the file it points to
+/// is replaced, or created if it doesn't exist yet, and the symlink is left alone.
+fn write_output<F>(output: Option<&Path>, write: F) -> Result<(), Error>
+where
+    F: FnOnce(&mut dyn Write) -> io::Result<()>,
+{
+    let output = match output {
+        Some(output) => output,
+        None => {
+            let stdout = io::stdout();
+            let mut out = BufWriter::new(stdout.lock());
+            return match write(&mut out).and_then(|()| out.flush()) {
+                // Whatever was reading the output, like `head`, has all it wants, so stop
+                // quietly, as sort(1) does.
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+                result => result.map_err(Error::from),
+            };
+        }
+    };
+
+    // Replace the file a symlink points to, not the symlink itself.
+    let target = resolve_symlinks(output).map_err(Error::io(output))?;
+    let metadata = fs::metadata(&target).ok();
+    if metadata.as_ref().map_or(false, |m| !m.is_file()) {
+        // Devices, pipes and the like can't be renamed over, and don't need to be.
+        let mut out = BufWriter::new(File::create(output).map_err(Error::io(output))?);
+        return write(&mut out)
+            .and_then(|()| out.flush())
+            .map_err(Error::io(output));
+    }
+
+    let (file, temp) = create_temp(&target).map_err(Error::io(output))?;
+    let result = (|| {
+        if let Some(metadata) = &metadata {
+            file.set_permissions(metadata.permissions())?;
+        }
+        let mut out = BufWriter::new(file);
+        write(&mut out)?;
+        out.into_inner()?.sync_all()?;
+        fs::rename(&temp, &target)
+    })();
+    if let Err(error) = result {
+        let _ = fs::remove_file(&temp);
+        return Err(Error::io(output)(error));
     }
+    Ok(())
+}
 
-    let mut nums: Vec<i64> = io::stdin()
-        .lock()
-        .lines()
-        .map(|s| s.unwrap().parse().unwrap())
-        .collect();
-    nums.stooge_sort();
+/// Follows `path` through any symlinks to the path they end up at, which needn't exist.
+fn resolve_symlinks(path: &Path) -> io::Result<PathBuf> {
+    let mut path = path.to_path_buf();
+    // The most that Linux will follow.
+    for _ in 0..40 {
+        match fs::symlink_metadata(&path) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                // Relative links are relative to the directory the link is in.
+                let link = fs::read_link(&path)?;
+                path = match path.parent() {
+                    Some(parent) => parent.join(link),
+                    None => link,
+                };
+            }
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => return Ok(path),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        "too many levels of symbolic links",
+    ))
+}
 
-    for n in nums {
-        println!("{}", n);
+/// Creates a new, empty file next to `target`, returning it and its path.
+///
+/// Its name is easy to guess, so it's only ever created from scratch: if anything
+/// already has the name, even a symlink, another name is tried instead of opening it.
+fn create_temp(target: &Path) -> io::Result<(File, PathBuf)> {
+    let name = target
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file name"))?;
+    let mut attempt: u32 = 0;
+    loop {
+        let mut temp_name = name.to_os_string();
+        temp_name.push(format!(".stoogesort-{}-{}.tmp", process::id(), attempt));
+        let temp = target.with_file_name(temp_name);
+        match OpenOptions::new().write(true).create_new(true).open(&temp) {
+            Ok(file) => return Ok((file, temp)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempt < 100 => attempt += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn parse_text(line: Vec<u8>) -> Result<String, String> {
+    String::from_utf8(line).map_err(|_| "invalid UTF-8 (use --bytes to sort it anyway)".to_string())
+}
+
+/// A line, along with the number to sort it by.
+type Keyed = (i64, Vec<u8>);
+
+/// Parses a line as an integer, keeping the line as it was for writing back out.
+fn parse_integer(line: Vec<u8>) -> Result<Keyed, String> {
+    let s = String::from_utf8_lossy(&line);
+    let n = s
+        .trim()
+        .parse()
+        .map_err(|e: ParseIntError| match e.kind() {
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                format!("integer out of range {:?}", s)
+            }
+            _ => format!("invalid integer {:?}", s),
+        })?;
+    Ok((n, line))
+}
+
+/// Parses a line as a float, keeping the line as it was for writing back out.
+///
+/// The float is turned into an integer that sorts in IEEE 754 totalOrder, the same way
+/// `StoogeFloats` does it.
+fn parse_float(line: Vec<u8>) -> Result<Keyed, String> {
+    let s = String::from_utf8_lossy(&line);
+    let x: f64 = s
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid number {:?}", s))?;
+    let bits = x.to_bits() as i64;
+    let key = bits ^ (((bits >> 63) as u64) >> 1) as i64;
+    Ok((key, line))
+}
+
+fn write_lines<'a, I>(out: &mut dyn Write, lines: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = &'a [u8]>,
+{
+    lines.into_iter().try_for_each(|line| {
+        out.write_all(line)?;
+        out.write_all(b"\n")
+    })
+}
+
+fn run() -> Result<(), Error> {
+    let options = parse_args()?;
+    let inputs = &options.inputs;
+    let skip_invalid = options.skip_invalid;
+    let output = options.output.as_deref();
+
+    match options.mode {
+        Mode::Lexicographic => {
+            let mut lines = read_inputs(inputs, skip_invalid, parse_text)?;
+            lines.stooge_sort();
+            write_output(output, |out| {
+                write_lines(out, lines.iter().map(|line| line.as_bytes()))
+            })
+        }
+        Mode::Numeric | Mode::Float => {
+            let parse: fn(Vec<u8>) -> Result<Keyed, String> = match options.mode {
+                Mode::Numeric => parse_integer,
+                _ => parse_float,
+            };
+            let mut lines = read_inputs(inputs, skip_invalid, parse)?;
+            // Sort on the numbers, but write out the lines just as they were. Lines with the
+            // same number, like `1` and `01`, fall back to comparing the lines themselves.
+            lines.stooge_sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+            write_output(output, |out| {
+                write_lines(out, lines.iter().map(|line| &line.1[..]))
+            })
+        }
+        Mode::Bytes => {
+            let mut lines = read_inputs(inputs, skip_invalid, Ok)?;
+            lines.stooge_sort();
+            write_output(output, |out| {
+                write_lines(out, lines.iter().map(|line| &line[..]))
+            })
+        }
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("stoogesort: {}", e);
+        if let Error::Usage(_) = e {
+            eprintln!("Try 'stoogesort --help' for more information.");
+        }
+        process::exit(e.exit_code());
     }
 }
@@ -0,0 +1,102 @@
+//! Sorting through shared references.
+
+use crate::{stooge_sort_access, StoogeAccess};
+use core::cell::Cell;
+use core::cmp::Ordering;
+
+/// Sorts a slice of [`Cell`]s using stooge sort.
+///
+/// This only needs a shared reference, so it works on buffers like `Rc<[Cell<T>]>` that
+/// many owners can see but none can get a `&mut` to. Elements are swapped with
+/// [`Cell::swap`].
+///
+/// This sort is unstable, has worst-case
+/// time complexity of O(n^(log(3)/log(1.5)))
+/// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+///
+/// ```
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use stoogesort::stooge_sort_cells;
+///
+/// let shared: Rc<[Cell<i32>]> = [5, 4, 1, 3, 2].iter().copied().map(Cell::new).collect();
+/// let other_owner = Rc::clone(&shared);
+///
+/// stooge_sort_cells(&shared);
+/// assert!(other_owner.iter().map(Cell::get).eq([1, 2, 3, 4, 5]));
+/// ```
+pub fn stooge_sort_cells<T: Copy + Ord>(cells: &[Cell<T>]) {
+    stooge_sort_cells_by(cells, T::cmp);
+}
+
+/// Sorts a slice of [`Cell`]s using stooge sort with a comparator function.
+///
+/// This sort is unstable, has worst-case
+/// time complexity of O(n^(log(3)/log(1.5)))
+/// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+///
+/// The comparator function must define a total ordering, as with
+/// [`stooge_sort_by`](crate::Stooge::stooge_sort_by). It's passed copies of the
+/// elements, so it can't see the cells change under it.
+///
+/// ```
+/// use std::cell::Cell;
+/// use stoogesort::stooge_sort_cells_by;
+///
+/// let cells = [Cell::new(1), Cell::new(3), Cell::new(2)];
+/// stooge_sort_cells_by(&cells, |a, b| b.cmp(a));
+/// assert!(cells.iter().map(Cell::get).eq([3, 2, 1]));
+/// ```
+pub fn stooge_sort_cells_by<T, F>(cells: &[Cell<T>], compare: F)
+where
+    T: Copy,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    stooge_sort_access(&mut CellAccess { cells, compare });
+}
+
+/// Compares and swaps the elements of a slice of `Cell`s by index.
+struct CellAccess<'a, T, F> {
+    cells: &'a [Cell<T>],
+    compare: F,
+}
+
+impl<T, F> StoogeAccess for CellAccess<'_, T, F>
+where
+    T: Copy,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    fn len(&self) -> usize {
+        self.cells.len()
+    }
+    fn less(&mut self, i: usize, j: usize) -> bool {
+        (self.compare)(&self.cells[i].get(), &self.cells[j].get()) == Ordering::Less
+    }
+    fn swap(&mut self, i: usize, j: usize) {
+        self.cells[i].swap(&self.cells[j]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_slice() {
+        crate::test_util::assert_sorts_like_stooge_sort(70, |pairs| {
+            let cells: Vec<Cell<(u8, usize)>> = pairs.into_iter().map(Cell::new).collect();
+            stooge_sort_cells_by(&cells, |a, b| a.0.cmp(&b.0));
+            cells.iter().map(Cell::get).collect()
+        });
+    }
+
+    #[test]
+    fn short() {
+        let empty: [Cell<u8>; 0] = [];
+        stooge_sort_cells(&empty);
+
+        let one = [Cell::new(1)];
+        stooge_sort_cells(&one);
+        assert_eq!(one[0].get(), 1);
+    }
+}
@@ -0,0 +1,222 @@
+//! Sorting `VecDeque`s and `LinkedList`s in place.
+
+use crate::{stooge_sort_access, StoogeAccess};
+use alloc::collections::{LinkedList, VecDeque};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::mem;
+
+/// Stooge sort for [`VecDeque`] and [`LinkedList`], without copying them into a slice
+/// first.
+///
+/// These make exactly the same comparisons and swaps as the methods of the same names on
+/// [`Stooge`](crate::Stooge), so they give the same result as sorting a slice of the
+/// same elements.
+///
+/// A `VecDeque` is sorted across both halves of its ring buffer, so there's no need to
+/// call `make_contiguous()`, and it never reallocates. A `LinkedList` is sorted by
+/// swapping values between its nodes, which stay where they are; it needs a `Vec` of one
+/// pointer per node to get at them by index.
+///
+/// ```
+/// use std::collections::{LinkedList, VecDeque};
+/// use stoogesort::StoogeSeq;
+///
+/// let mut deque = VecDeque::from(vec![3, 4, 5]);
+/// deque.push_front(2);
+/// deque.push_front(1);
+/// deque.stooge_sort_by(|a, b| b.cmp(a));
+/// assert_eq!(deque, [5, 4, 3, 2, 1]);
+///
+/// let mut list: LinkedList<_> = ["bb", "a", "ccc"].into_iter().collect();
+/// list.stooge_sort_by_key(|s| s.len());
+/// assert!(list.into_iter().eq(["a", "bb", "ccc"]));
+/// ```
+pub trait StoogeSeq<T> {
+    /// Sorts the collection using stooge sort.
+    ///
+    /// This sort is unstable, has worst-case
+    /// time complexity of O(n^(log(3)/log(1.5)))
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+    fn stooge_sort(&mut self)
+    where
+        T: Ord;
+    /// Sorts the collection using stooge sort with a comparator function.
+    ///
+    /// This sort is unstable, has worst-case
+    /// time complexity of O(n^(log(3)/log(1.5)))
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// The comparator function must define a total ordering, as with
+    /// [`Stooge::stooge_sort_by`](crate::Stooge::stooge_sort_by).
+    fn stooge_sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering;
+    /// Sorts the collection using stooge sort with a key extraction function.
+    ///
+    /// This sort is unstable, has worst-case
+    /// time complexity of O(n^(log(3)/log(1.5)) * m)
+    /// ≈ O(n^2.7095 * m), where the key function is O(m),
+    /// and doesn't recurse (see [Recursion](crate#recursion)).
+    fn stooge_sort_by_key<F, K>(&mut self, compare: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord;
+}
+
+/// Compares and swaps the elements of a `VecDeque` by index.
+struct DequeAccess<'a, T, F> {
+    deque: &'a mut VecDeque<T>,
+    compare: F,
+}
+
+impl<T, F> StoogeAccess for DequeAccess<'_, T, F>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    fn len(&self) -> usize {
+        self.deque.len()
+    }
+    fn less(&mut self, i: usize, j: usize) -> bool {
+        (self.compare)(&self.deque[i], &self.deque[j]) == Ordering::Less
+    }
+    fn swap(&mut self, i: usize, j: usize) {
+        self.deque.swap(i, j);
+    }
+}
+
+impl<T> StoogeSeq<T> for VecDeque<T> {
+    fn stooge_sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.stooge_sort_by(T::cmp);
+    }
+    fn stooge_sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        stooge_sort_access(&mut DequeAccess {
+            deque: self,
+            compare,
+        });
+    }
+    fn stooge_sort_by_key<F, K>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.stooge_sort_by(|a, b| compare(a).cmp(&compare(b)));
+    }
+}
+
+/// Compares and swaps values through a table of references to them.
+struct TableAccess<'a, 'b, T, F> {
+    table: &'a mut [&'b mut T],
+    compare: F,
+}
+
+impl<T, F> StoogeAccess for TableAccess<'_, '_, T, F>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    fn len(&self) -> usize {
+        self.table.len()
+    }
+    fn less(&mut self, i: usize, j: usize) -> bool {
+        (self.compare)(self.table[i], self.table[j]) == Ordering::Less
+    }
+    fn swap(&mut self, i: usize, j: usize) {
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        if i != j {
+            let (low, high) = self.table.split_at_mut(j);
+            mem::swap(&mut *low[i], &mut *high[0]);
+        }
+    }
+}
+
+impl<T> StoogeSeq<T> for LinkedList<T> {
+    fn stooge_sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.stooge_sort_by(T::cmp);
+    }
+    fn stooge_sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.len() < 2 {
+            return;
+        }
+
+        let mut table: Vec<&mut T> = self.iter_mut().collect();
+        stooge_sort_access(&mut TableAccess {
+            table: &mut table,
+            compare,
+        });
+    }
+    fn stooge_sort_by_key<F, K>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.stooge_sort_by(|a, b| compare(a).cmp(&compare(b)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_sorts_like_stooge_sort;
+
+    #[test]
+    fn deque_matches_slice() {
+        assert_sorts_like_stooge_sort(70, |pairs| {
+            // Start partway through the buffer so the elements wrap around the end.
+            let half = pairs.len() / 2;
+            let mut deque = VecDeque::with_capacity(pairs.len());
+            for &pair in pairs[half..].iter() {
+                deque.push_back(pair);
+            }
+            for &pair in pairs[..half].iter().rev() {
+                deque.push_front(pair);
+            }
+            let capacity = deque.capacity();
+            if pairs.len() >= 2 {
+                let (front, back) = deque.as_slices();
+                assert!(!front.is_empty() && !back.is_empty());
+            }
+
+            deque.stooge_sort_by_key(|p| p.0);
+
+            assert_eq!(deque.capacity(), capacity);
+            deque.into_iter().collect()
+        });
+    }
+
+    #[test]
+    fn list_matches_slice() {
+        assert_sorts_like_stooge_sort(70, |pairs| {
+            let mut list: LinkedList<_> = pairs.into_iter().collect();
+            let nodes: Vec<*const (u8, usize)> = list.iter().map(|p| p as *const _).collect();
+
+            list.stooge_sort_by_key(|p| p.0);
+
+            assert!(list.iter().map(|p| p as *const _).eq(nodes));
+            list.into_iter().collect()
+        });
+    }
+
+    #[test]
+    fn short() {
+        let mut deque: VecDeque<u8> = VecDeque::new();
+        deque.stooge_sort();
+        assert!(deque.is_empty());
+
+        let mut list: LinkedList<u8> = LinkedList::new();
+        list.push_back(1);
+        list.stooge_sort();
+        assert!(list.into_iter().eq([1]));
+    }
+}
@@ -0,0 +1,134 @@
+//! Sorting arrays at compile time.
+
+use crate::STACK_CAPACITY;
+
+macro_rules! const_sort {
+    ($($name:ident: $t:ty => $example:expr,)*) => {$(
+        #[doc = concat!("Sorts an array of [`", stringify!($t), "`]s using stooge sort, at compile time if need be.")]
+        ///
+        /// This is a `const fn`, so it can sort lookup tables as the compiler builds them.
+        /// It makes the same comparisons and swaps as [`stooge_sort`](crate::Stooge::stooge_sort),
+        /// walking the same fixed-size stack (see [Recursion](crate#recursion)), since
+        /// trait methods can't be called in constant expressions.
+        ///
+        /// This sort is unstable, has worst-case
+        /// time complexity of O(n^(log(3)/log(1.5)))
+        /// ≈ O(n^2.7095), and doesn't recurse.
+        ///
+        /// ```
+        #[doc = concat!("use stoogesort::", stringify!($name), ";")]
+        ///
+        #[doc = concat!("const RAW: [", stringify!($t), "; 4] = ", stringify!($example), ";")]
+        #[doc = concat!("const SORTED: [", stringify!($t), "; 4] = ", stringify!($name), "(RAW);")]
+        ///
+        /// let mut expected = RAW;
+        /// expected.sort();
+        /// assert_eq!(SORTED, expected);
+        /// ```
+        pub const fn $name<const N: usize>(mut v: [$t; N]) -> [$t; N] {
+            if N < 2 {
+                return v;
+            }
+
+            // The same frames as `WorkStack`, visited in the same order, except that
+            // there's nobody to tell about a call finishing, so there's no phase 3.
+            let mut frames = [(0, 0, 0u8); STACK_CAPACITY];
+            frames[0] = (0, N - 1, 0);
+            let mut len = 1;
+
+            while len > 0 {
+                len -= 1;
+                let (left, right, phase) = frames[len];
+                let third = (right - left + 1) / 3;
+                match phase {
+                    0 => {
+                        if !(v[left] < v[right]) {
+                            let tmp = v[left];
+                            v[left] = v[right];
+                            v[right] = tmp;
+                        }
+                        if (right - left + 1) > 2 {
+                            frames[len] = (left, right, 1);
+                            frames[len + 1] = (left, right - third, 0);
+                            len += 2;
+                        }
+                    }
+                    1 => {
+                        frames[len] = (left, right, 2);
+                        frames[len + 1] = (left + third, right, 0);
+                        len += 2;
+                    }
+                    _ => {
+                        frames[len] = (left, right - third, 0);
+                        len += 1;
+                    }
+                }
+            }
+            v
+        }
+    )*};
+}
+
+const_sort! {
+    const_sort_i8: i8 => [3, -128, 0, 127],
+    const_sort_i16: i16 => [3, -1, 0, 2],
+    const_sort_i32: i32 => [3, -1, 0, 2],
+    const_sort_i64: i64 => [3, -1, 0, 2],
+    const_sort_i128: i128 => [3, -1, 0, 2],
+    const_sort_isize: isize => [3, -1, 0, 2],
+    const_sort_u8: u8 => [3, 255, 0, 2],
+    const_sort_u16: u16 => [3, 1, 0, 2],
+    const_sort_u32: u32 => [3, 1, 0, 2],
+    const_sort_u64: u64 => [3, 1, 0, 2],
+    const_sort_u128: u128 => [3, 1, 0, 2],
+    const_sort_usize: usize => [3, 1, 0, 2],
+    const_sort_char: char => ['s', 't', 'o', 'g'],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Stooge;
+    use rand::{distributions::Standard, Rng};
+
+    const RAW: [u32; 16] = [9, 3, 14, 0, 7, 7, 12, 1, 15, 4, 10, 2, 13, 6, 11, 5];
+    const SORTED: [u32; 16] = const_sort_u32(RAW);
+
+    #[test]
+    fn compile_time() {
+        assert_eq!(
+            SORTED,
+            [0, 1, 2, 3, 4, 5, 6, 7, 7, 9, 10, 11, 12, 13, 14, 15]
+        );
+    }
+
+    #[test]
+    fn short() {
+        const EMPTY: [char; 0] = const_sort_char([]);
+        const ONE: [char; 1] = const_sort_char(['a']);
+        const TWO: [i8; 2] = const_sort_i8([1, -1]);
+        assert_eq!(EMPTY, []);
+        assert_eq!(ONE, ['a']);
+        assert_eq!(TWO, [-1, 1]);
+    }
+
+    #[test]
+    fn matches_slice() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..10 {
+            let mut v: [i64; 50] = [0; 50];
+            rng.fill(&mut v[..]);
+            let sorted = const_sort_i64(v);
+            v.stooge_sort();
+            assert_eq!(sorted, v);
+
+            let mut v: [char; 30] = ['\0'; 30];
+            for c in v.iter_mut() {
+                *c = rng.sample(Standard);
+            }
+            let sorted = const_sort_char(v);
+            v.stooge_sort();
+            assert_eq!(sorted, v);
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! Constant-time sorting for secret data.
+
+use crate::WorkStack;
+use core::ops::{BitAnd, BitXor};
+
+/// Constant-time stooge sort for slices of integers.
+///
+/// Stooge sort always compare-exchanges the same pairs of indices for a given length
+/// (it's a sorting network; see [`apply_network`](crate::apply_network)), so the only
+/// thing left that could depend on the data is the compare-exchange itself. This one
+/// doesn't branch: it works out whether to swap as an all-zeros or all-ones mask using
+/// only arithmetic and bitwise operations, then always writes both elements back, XORed
+/// with the masked difference. So the instructions run and the memory touched depend
+/// only on the length of the slice, never on its contents.
+///
+/// The mask is passed through a volatile read to keep the optimizer from turning it
+/// back into a branch. That's the best a library can do, but it isn't a guarantee:
+/// if it matters, check the generated code for your target.
+///
+/// ```
+/// use stoogesort::StoogeCt;
+/// let mut secrets = [0x5eed_u32, 42, 0xdead_beef, 7];
+///
+/// secrets.stooge_sort_ct();
+/// assert_eq!(secrets, [7, 42, 0x5eed, 0xdead_beef]);
+/// ```
+pub trait StoogeCt {
+    /// Sorts the slice using stooge sort, in constant time.
+    ///
+    /// This sort is unstable (though equal integers can't be told apart anyway), has
+    /// worst-case and best-case time complexity of O(n^(log(3)/log(1.5)))
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+    fn stooge_sort_ct(&mut self);
+}
+
+/// An integer that can be compare-exchanged without branching.
+trait Word: Copy + BitAnd<Output = Self> + BitXor<Output = Self> {
+    /// Returns all ones if `a` and `b` need swapping (that is, unless `a < b`), or all
+    /// zeros if not, without branching.
+    fn swap_mask(a: Self, b: Self) -> Self;
+}
+
+macro_rules! impl_stooge_ct {
+    ($($unsigned:ty, $signed:ty;)*) => {$(
+        impl Word for $unsigned {
+            fn swap_mask(a: Self, b: Self) -> Self {
+                // The top bit of this is set exactly when a < b (as in BearSSL's LT).
+                let less = (a ^ ((a ^ b) | (a.wrapping_sub(b) ^ b))) >> (<$unsigned>::BITS - 1);
+                less.wrapping_sub(1)
+            }
+        }
+
+        impl Word for $signed {
+            fn swap_mask(a: Self, b: Self) -> Self {
+                // Flipping the sign bit maps signed order onto unsigned order.
+                let bias = 1 << (<$unsigned>::BITS - 1);
+                <$unsigned>::swap_mask(a as $unsigned ^ bias, b as $unsigned ^ bias) as $signed
+            }
+        }
+
+        impl StoogeCt for [$unsigned] {
+            fn stooge_sort_ct(&mut self) {
+                stooge_sort_ct(self);
+            }
+        }
+
+        impl StoogeCt for [$signed] {
+            fn stooge_sort_ct(&mut self) {
+                stooge_sort_ct(self);
+            }
+        }
+    )*};
+}
+
+impl_stooge_ct! {
+    u8, i8;
+    u16, i16;
+    u32, i32;
+    u64, i64;
+    u128, i128;
+    usize, isize;
+}
+
+/// Somewhere to sort, so that tests can watch every load and store.
+trait Memory<W> {
+    fn len(&self) -> usize;
+    fn load(&mut self, i: usize) -> W;
+    fn store(&mut self, i: usize, x: W);
+}
+
+impl<W: Copy> Memory<W> for [W] {
+    fn len(&self) -> usize {
+        <[W]>::len(self)
+    }
+    fn load(&mut self, i: usize) -> W {
+        self[i]
+    }
+    fn store(&mut self, i: usize, x: W) {
+        self[i] = x;
+    }
+}
+
+/// Hides `x` from the optimizer, so that it can't tell a mask is all zeros or all ones.
+fn barrier<W: Copy>(x: W) -> W {
+    // SAFETY: `&x` is a valid, aligned pointer to an initialized `W`, and `W: Copy`.
+    unsafe { core::ptr::read_volatile(&x) }
+}
+
+fn stooge_sort_ct<W, M>(v: &mut M)
+where
+    W: Word,
+    M: Memory<W> + ?Sized,
+{
+    if v.len() < 2 {
+        return;
+    }
+
+    let mut work = WorkStack::new(0, v.len() - 1);
+
+    while let Some((left, right)) = work.next(&mut ()) {
+        let a = v.load(left);
+        let b = v.load(right);
+        let diff = (a ^ b) & barrier(W::swap_mask(a, b));
+        v.store(left, a ^ diff);
+        v.store(right, b ^ diff);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Stooge;
+    use rand::Rng;
+
+    #[derive(Debug, PartialEq)]
+    enum Access {
+        Load(usize),
+        Store(usize),
+    }
+
+    /// A slice that records every access made to it.
+    struct Traced<'a, W> {
+        v: &'a mut [W],
+        trace: Vec<Access>,
+    }
+
+    impl<W: Copy> Memory<W> for Traced<'_, W> {
+        fn len(&self) -> usize {
+            self.v.len()
+        }
+        fn load(&mut self, i: usize) -> W {
+            self.trace.push(Access::Load(i));
+            self.v[i]
+        }
+        fn store(&mut self, i: usize, x: W) {
+            self.trace.push(Access::Store(i));
+            self.v[i] = x;
+        }
+    }
+
+    fn trace<W: Word>(v: &mut [W]) -> Vec<Access> {
+        let mut traced = Traced {
+            v,
+            trace: Vec::new(),
+        };
+        stooge_sort_ct(&mut traced);
+        traced.trace
+    }
+
+    #[test]
+    fn swap_mask() {
+        for a in 0..=u8::MAX {
+            for b in 0..=u8::MAX {
+                let expected = if a < b { 0 } else { u8::MAX };
+                assert_eq!(u8::swap_mask(a, b), expected);
+
+                let (a, b) = (a as i8, b as i8);
+                let expected = if a < b { 0 } else { -1 };
+                assert_eq!(i8::swap_mask(a, b), expected);
+            }
+        }
+        assert_eq!(u128::swap_mask(u128::MAX, 0), u128::MAX);
+        assert_eq!(i64::swap_mask(i64::MIN, i64::MAX), 0);
+        assert_eq!(isize::swap_mask(-1, -1), -1);
+    }
+
+    #[test]
+    fn matches_stooge_sort() {
+        let mut rng = rand::thread_rng();
+        for len in 0..40 {
+            let mut v: Vec<i32> = (0..len).map(|_| rng.gen_range(-5..5)).collect();
+            let mut expected = v.clone();
+            v.stooge_sort_ct();
+            expected.stooge_sort();
+            assert_eq!(v, expected);
+
+            let mut v: Vec<u64> = (0..len).map(|_| rng.gen()).collect();
+            let mut expected = v.clone();
+            v.stooge_sort_ct();
+            expected.stooge_sort();
+            assert_eq!(v, expected);
+        }
+    }
+
+    #[test]
+    fn accesses_are_input_independent() {
+        let mut rng = rand::thread_rng();
+        for len in 0..30 {
+            let mut sorted: Vec<u16> = (0..len as u16).collect();
+            let mut reversed: Vec<u16> = sorted.iter().rev().copied().collect();
+            let mut equal = vec![7_u16; len];
+            let mut random: Vec<u16> = (0..len).map(|_| rng.gen()).collect();
+
+            let expected = trace(&mut sorted);
+            assert_eq!(trace(&mut reversed), expected);
+            assert_eq!(trace(&mut equal), expected);
+            assert_eq!(trace(&mut random), expected);
+
+            let mut signed: Vec<i16> = (0..len).map(|_| rng.gen()).collect();
+            assert_eq!(trace(&mut signed), expected);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Sorting floats, NaNs and all.
+
+use crate::Stooge;
+
+/// Stooge sort for slices of floats, using the IEEE 754 totalOrder predicate.
+///
+/// Unlike [`partial_cmp`](PartialOrd::partial_cmp), totalOrder can compare anything,
+/// NaNs included, so there's no need to unwrap. It orders values like so:
+///
+/// * negative quiet NaN
+/// * negative signaling NaN
+/// * negative infinity
+/// * negative numbers
+/// * negative subnormal numbers
+/// * negative zero
+/// * positive zero
+/// * positive subnormal numbers
+/// * positive numbers
+/// * positive infinity
+/// * positive signaling NaN
+/// * positive quiet NaN
+///
+/// This is the same order as `f64::total_cmp` (which isn't available on this
+/// crate's minimum supported Rust version).
+pub trait StoogeFloats {
+    /// Sorts the slice using stooge sort, ordering the floats by IEEE 754 totalOrder.
+    ///
+    /// This sort is unstable, has worst-case
+    /// time complexity of O(n^(log(3)/log(1.5)))
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// Negative zero sorts before positive zero, and NaNs sort to either end
+    /// depending on their sign bit.
+    ///
+    /// ```
+    /// use stoogesort::StoogeFloats;
+    /// let mut floats = [1.0, f64::NAN, 0.0, -0.0, f64::NEG_INFINITY, -1.6];
+    ///
+    /// floats.stooge_sort_floats();
+    /// assert_eq!(floats[..5], [f64::NEG_INFINITY, -1.6, -0.0, 0.0, 1.0]);
+    /// assert!(floats[2].is_sign_negative() && floats[3].is_sign_positive());
+    /// assert!(floats[5].is_nan());
+    /// ```
+    fn stooge_sort_floats(&mut self);
+}
+
+macro_rules! impl_stooge_floats {
+    ($float:ty, $signed:ty, $unsigned:ty) => {
+        impl StoogeFloats for [$float] {
+            fn stooge_sort_floats(&mut self) {
+                // Flipping every bit but the sign of a negative float makes the bits,
+                // read as a two's complement integer, count up in totalOrder.
+                fn key(x: &$float) -> $signed {
+                    let bits = x.to_bits() as $signed;
+                    let sign = (bits >> (<$signed>::BITS - 1)) as $unsigned;
+                    bits ^ (sign >> 1) as $signed
+                }
+
+                self.stooge_sort_by(|a, b| key(a).cmp(&key(b)));
+            }
+        }
+    };
+}
+
+impl_stooge_floats!(f32, i32, u32);
+impl_stooge_floats!(f64, i64, u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{distributions::Uniform, Rng};
+
+    #[test]
+    fn total_order() {
+        let neg_nan = -f64::NAN;
+        let mut test = [
+            f64::INFINITY,
+            0.0,
+            f64::NAN,
+            -0.0,
+            f64::MIN_POSITIVE / 2.0,
+            neg_nan,
+            -1.0,
+            f64::NEG_INFINITY,
+            1.0,
+        ];
+        test.stooge_sort_floats();
+
+        let bits: Vec<u64> = test.iter().map(|x| x.to_bits()).collect();
+        let expected: Vec<u64> = [
+            neg_nan,
+            f64::NEG_INFINITY,
+            -1.0,
+            -0.0,
+            0.0,
+            f64::MIN_POSITIVE / 2.0,
+            1.0,
+            f64::INFINITY,
+            f64::NAN,
+        ]
+        .iter()
+        .map(|x| x.to_bits())
+        .collect();
+        assert_eq!(bits, expected);
+    }
+
+    #[test]
+    fn f32_total_order() {
+        let mut test = [f32::NAN, 2.5, -0.0, -f32::NAN, 0.0, f32::NEG_INFINITY];
+        test.stooge_sort_floats();
+
+        assert!(test[0].is_nan() && test[0].is_sign_negative());
+        assert_eq!(test[1..5], [f32::NEG_INFINITY, -0.0, 0.0, 2.5]);
+        assert!(test[2].is_sign_negative() && test[3].is_sign_positive());
+        assert!(test[5].is_nan() && test[5].is_sign_positive());
+    }
+
+    #[test]
+    fn matches_partial_cmp_without_nans() {
+        let mut rng = rand::thread_rng();
+        let range = Uniform::new(-1000.0, 1000.0);
+        let mut test: Vec<f64> = (1..=100).map(|_| rng.sample(range)).collect();
+        let mut reference: Vec<f64> = test.clone();
+
+        test.stooge_sort_floats();
+        reference.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        assert_eq!(test, reference);
+    }
+}
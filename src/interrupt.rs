@@ -0,0 +1,218 @@
+//! Stopping a sort before it's finished.
+
+use crate::WorkStack;
+use core::fmt;
+
+/// Why a sort was [`Interrupted`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InterruptReason {
+    /// The sort needed more comparisons than it was allowed.
+    BudgetExhausted,
+    /// The sort was cancelled.
+    Cancelled,
+}
+
+/// The error returned when a sort is stopped before it's finished.
+///
+/// The slice being sorted is left partially sorted, but it's still a permutation of the
+/// input: stooge sort only ever swaps elements.
+///
+/// Returned by [`try_stooge_sort_by_with_budget`](crate::Stooge::try_stooge_sort_by_with_budget)
+/// and [`try_stooge_sort_by_with_cancel`](crate::Stooge::try_stooge_sort_by_with_cancel).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interrupted {
+    reason: InterruptReason,
+    comparisons: u64,
+    total_comparisons: u64,
+}
+
+impl Interrupted {
+    /// Returns why the sort was stopped.
+    pub fn reason(&self) -> InterruptReason {
+        self.reason
+    }
+
+    /// Returns the number of comparisons made before the sort was stopped.
+    pub fn comparisons(&self) -> u64 {
+        self.comparisons
+    }
+
+    /// Returns the number of comparisons the whole sort would have made.
+    ///
+    /// This depends only on the length of the slice. It saturates at [`u64::MAX`]
+    /// (you'd be waiting a while anyway).
+    pub fn total_comparisons(&self) -> u64 {
+        self.total_comparisons
+    }
+}
+
+impl fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self.reason {
+            InterruptReason::BudgetExhausted => "comparison budget exhausted",
+            InterruptReason::Cancelled => "cancelled",
+        };
+        write!(
+            f,
+            "sort stopped after {} of {} comparisons: {}",
+            self.comparisons, self.total_comparisons, reason
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Interrupted {}
+
+/// Sorts `v[left..=right]`, asking `stop` before every comparison whether to give up.
+///
+/// `stop` is passed the number of comparisons made so far.
+pub(crate) fn stooge_sort_until<T, F, S>(
+    v: &mut [T],
+    left: usize,
+    right: usize,
+    is_less: &mut F,
+    stop: &mut S,
+) -> Result<(), Interrupted>
+where
+    F: FnMut(&T, &T) -> bool,
+    S: FnMut(u64) -> Option<InterruptReason>,
+{
+    let mut work = WorkStack::new(left, right);
+    let mut comparisons = 0;
+
+    while let Some((left, right)) = work.next(&mut ()) {
+        if let Some(reason) = stop(comparisons) {
+            return Err(Interrupted {
+                reason,
+                comparisons,
+                total_comparisons: total_comparisons(v.len()),
+            });
+        }
+
+        comparisons += 1;
+        if !is_less(&v[left], &v[right]) {
+            v.swap(left, right);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the number of comparisons needed to stooge sort `len` elements.
+///
+/// All three recursive sorts are the same length, so C(n) = 1 + 3 * C(n - n / 3)
+/// unrolls into a sum of powers of 3.
+fn total_comparisons(len: usize) -> u64 {
+    let mut total: u64 = 0;
+    let mut calls: u64 = 1;
+    let mut len = len;
+
+    while len >= 2 {
+        total = total.saturating_add(calls);
+        if len == 2 {
+            break;
+        }
+        calls = calls.saturating_mul(3);
+        len -= len / 3;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Stooge;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn total_comparisons_matches_stats() {
+        for len in 0..=60 {
+            let mut test: Vec<usize> = (0..len).collect();
+            let stats = test.stooge_sort_with_stats();
+            assert_eq!(total_comparisons(len), stats.comparisons);
+        }
+        assert_eq!(total_comparisons(usize::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn exact_budget_is_enough() {
+        let mut test: Vec<i32> = (0..20).rev().collect();
+        let needed = total_comparisons(test.len());
+        assert!(test
+            .clone()
+            .try_stooge_sort_by_with_budget(needed - 1, i32::cmp)
+            .is_err());
+        assert_eq!(
+            test.try_stooge_sort_by_with_budget(needed, i32::cmp),
+            Ok(())
+        );
+        assert_eq!(test, (0..20).collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn zero_budget() {
+        let mut test = [1, 0];
+        let err = test
+            .try_stooge_sort_by_with_budget(0, i32::cmp)
+            .unwrap_err();
+        assert_eq!(err.comparisons(), 0);
+        assert_eq!(err.total_comparisons(), 1);
+        assert_eq!(test, [1, 0]);
+
+        let mut test = [0];
+        assert_eq!(test.try_stooge_sort_by_with_budget(0, i32::cmp), Ok(()));
+    }
+
+    #[test]
+    fn interrupted_matches_stepper() {
+        use crate::{StoogeStep, StoogeStepper};
+
+        let mut test: Vec<u32> = (0..50).map(|n| (n * 37) % 50).collect();
+        let mut reference = test.clone();
+
+        test.try_stooge_sort_by_with_budget(5000, u32::cmp)
+            .unwrap_err();
+
+        let mut stepper = StoogeStepper::new(&mut reference, u32::cmp);
+        for _ in 0..5000 {
+            // Each comparison is followed by its swap, if it has one.
+            if let Some(StoogeStep::Compare { less: false, .. }) = stepper.step() {
+                stepper.step();
+            }
+        }
+        assert_eq!(stepper.as_slice(), &test[..]);
+
+        test.sort();
+        assert_eq!(test, (0..50).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn already_cancelled() {
+        let cancel = AtomicBool::new(true);
+        let mut test = [3, 2, 1];
+        let err = test
+            .try_stooge_sort_by_with_cancel(&cancel, i32::cmp)
+            .unwrap_err();
+        assert_eq!(err.reason(), InterruptReason::Cancelled);
+        assert_eq!(err.comparisons(), 0);
+        assert_eq!(test, [3, 2, 1]);
+
+        cancel.store(false, Ordering::Relaxed);
+        assert_eq!(
+            test.try_stooge_sort_by_with_cancel(&cancel, i32::cmp),
+            Ok(())
+        );
+        assert_eq!(test, [1, 2, 3]);
+    }
+
+    #[test]
+    fn display() {
+        let mut test = [3, 2, 1];
+        let err = test
+            .try_stooge_sort_by_with_budget(2, i32::cmp)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "sort stopped after 2 of 4 comparisons: comparison budget exhausted"
+        );
+    }
+}
@@ -1,12 +1,90 @@
 #![doc = include_str!("../README.md")]
-use std::cmp::Ordering;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-pub trait Stooge<T> {
+#[cfg(feature = "alloc")]
+extern crate alloc;
+// So that the docs can link to `std` even when it isn't otherwise used.
+#[cfg(all(doc, not(feature = "std")))]
+extern crate std;
+
+use core::cmp::Ordering;
+use core::ops::RangeBounds;
+use core::sync::atomic::AtomicBool;
+
+mod access;
+#[cfg(feature = "alloc")]
+mod allocating;
+mod cell;
+#[cfg(feature = "alloc")]
+mod collections;
+mod const_sort;
+mod ct;
+mod float;
+mod interrupt;
+mod network;
+mod observer;
+// The `parallel` feature needs scoped threads.
+#[cfg(feature = "parallel")]
+#[clippy::msrv = "1.63"]
+mod parallel;
+mod partial;
+#[cfg(feature = "alloc")]
+mod permutation;
+mod range;
+mod stats;
+mod stepper;
+#[cfg(test)]
+mod test_util;
+mod zip;
+
+pub use access::{stooge_sort_access, StoogeAccess};
+#[cfg(feature = "alloc")]
+pub use allocating::StoogeAlloc;
+pub use cell::{stooge_sort_cells, stooge_sort_cells_by};
+#[cfg(feature = "alloc")]
+pub use collections::StoogeSeq;
+pub use const_sort::{
+    const_sort_char, const_sort_i128, const_sort_i16, const_sort_i32, const_sort_i64,
+    const_sort_i8, const_sort_isize, const_sort_u128, const_sort_u16, const_sort_u32,
+    const_sort_u64, const_sort_u8, const_sort_usize,
+};
+pub use ct::StoogeCt;
+pub use float::StoogeFloats;
+pub use interrupt::{InterruptReason, Interrupted};
+pub use network::{apply_network, apply_network_by};
+#[cfg(feature = "alloc")]
+pub use network::{
+    network, stooge_sort_batch, stooge_sort_batch_by, stooge_sort_chunks, stooge_sort_chunks_by,
+};
+pub use observer::StoogeObserver;
+#[cfg(feature = "parallel")]
+pub use parallel::StoogePar;
+pub use partial::{IncomparableError, PartialPolicy};
+#[cfg(feature = "alloc")]
+pub use permutation::apply_permutation;
+pub use range::RangeError;
+pub use stats::StoogeStats;
+pub use stepper::{StoogeStep, StoogeStepper};
+pub use zip::{stooge_sort_zip, stooge_sort_zip_by, Columns};
+
+mod sealed {
+    /// Keeps [`Stooge`](crate::Stooge) and `StoogeAlloc` from being implemented outside
+    /// this crate.
+    pub trait Sealed {}
+
+    impl<T> Sealed for [T] {}
+}
+
+/// Stooge sort for slices.
+///
+/// This trait is sealed, so that it can gain methods without breaking anything: it's
+/// only implemented for `[T]`, and can't be implemented outside this crate.
+pub trait Stooge<T>: sealed::Sealed {
     /// Sorts the slice using stooge sort.
     ///
     /// This sort is unstable, has worst-case
     /// time complexity of O(n^(log(3)/log(1.5)))
-    /// ≈ O(n^2.7095), and recurses at most n levels deep.
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
     ///
     /// ```
     /// use stoogesort::Stooge;
@@ -22,7 +100,7 @@ pub trait Stooge<T> {
     ///
     /// This sort is unstable, has worst-case
     /// time complexity of O(n^(log(3)/log(1.5)))
-    /// ≈ O(n^2.7095), and recurses at most n levels deep.
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
     ///
     /// The comparator function must define a total ordering for the elements in the slice. If
     /// the ordering is not total, the order of the elements is unspecified. An order is a
@@ -48,7 +126,7 @@ pub trait Stooge<T> {
     /// This sort is unstable, has worst-case
     /// time complexity of O(n^(log(3)/log(1.5)) * m)
     /// ≈ O(n^2.7095 * m), where the key function is O(m),
-    /// and recurses at most n levels deep.
+    /// and doesn't recurse (see [Recursion](crate#recursion)).
     ///
     /// # Examples
     ///
@@ -62,6 +140,297 @@ pub trait Stooge<T> {
     where
         F: FnMut(&T) -> K,
         K: Ord;
+    /// Sorts the slice using stooge sort, counting the work done along the way.
+    ///
+    /// This performs exactly the same comparisons and swaps as
+    /// [`stooge_sort`](Stooge::stooge_sort); see [`StoogeStats`] for what's counted.
+    ///
+    /// ```
+    /// use stoogesort::Stooge;
+    /// let mut v = [-5, 4, 1, -3, 2];
+    ///
+    /// let stats = v.stooge_sort_with_stats();
+    /// assert!(v == [-5, -3, 1, 2, 4]);
+    /// assert_eq!(stats.comparisons, 40);
+    /// assert_eq!(stats.max_depth, 4);
+    /// ```
+    fn stooge_sort_with_stats(&mut self) -> StoogeStats
+    where
+        T: Ord;
+    /// Sorts the slice using stooge sort with a comparator function, counting the work
+    /// done along the way.
+    ///
+    /// This performs exactly the same comparisons and swaps as
+    /// [`stooge_sort_by`](Stooge::stooge_sort_by); see [`StoogeStats`] for what's counted.
+    ///
+    /// ```
+    /// use stoogesort::Stooge;
+    /// let mut floats = [5f64, 4.0, 1.0, 3.0, 2.0];
+    /// let stats = floats.stooge_sort_by_with_stats(|a, b| a.partial_cmp(b).unwrap());
+    /// assert_eq!(floats, [1.0, 2.0, 3.0, 4.0, 5.0]);
+    /// assert_eq!(stats.calls, stats.comparisons);
+    /// ```
+    fn stooge_sort_by_with_stats<F>(&mut self, compare: F) -> StoogeStats
+    where
+        F: FnMut(&T, &T) -> Ordering;
+    /// Sorts the slice using stooge sort, reporting every step to `observer`.
+    ///
+    /// This performs exactly the same comparisons and swaps as
+    /// [`stooge_sort`](Stooge::stooge_sort); see [`StoogeObserver`] for when each
+    /// callback is called.
+    ///
+    /// ```
+    /// use stoogesort::{Stooge, StoogeObserver};
+    ///
+    /// #[derive(Default)]
+    /// struct SwapLog(Vec<(usize, usize)>);
+    ///
+    /// impl StoogeObserver for SwapLog {
+    ///     fn on_swap(&mut self, i: usize, j: usize) {
+    ///         self.0.push((i, j));
+    ///     }
+    /// }
+    ///
+    /// let mut v = [2, 0, 1];
+    /// let mut log = SwapLog::default();
+    ///
+    /// v.stooge_sort_observed(&mut log);
+    /// assert!(v == [0, 1, 2]);
+    /// assert_eq!(log.0, [(0, 2), (0, 1)]);
+    /// ```
+    fn stooge_sort_observed<O>(&mut self, observer: &mut O)
+    where
+        T: Ord,
+        O: StoogeObserver + ?Sized;
+    /// Sorts the slice using stooge sort with a comparator function, reporting every
+    /// step to `observer`.
+    ///
+    /// This performs exactly the same comparisons and swaps as
+    /// [`stooge_sort_by`](Stooge::stooge_sort_by); see [`StoogeObserver`] for when each
+    /// callback is called.
+    fn stooge_sort_by_observed<F, O>(&mut self, compare: F, observer: &mut O)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        O: StoogeObserver + ?Sized;
+    /// Sorts the slice using stooge sort with a comparator function, giving up after
+    /// `max_comparisons` comparisons.
+    ///
+    /// Stooge sort needs O(n^(log(3)/log(1.5))) ≈ O(n^2.7095) comparisons, so this
+    /// is a way to bound how long it takes. If the sort doesn't finish within budget,
+    /// an [`Interrupted`] error says how far it got, and the slice is left partially
+    /// sorted (but is still a permutation of the input). Otherwise, the result is the
+    /// same as [`stooge_sort_by`](Stooge::stooge_sort_by).
+    ///
+    /// ```
+    /// use stoogesort::{InterruptReason, Stooge};
+    /// let mut v: Vec<u32> = (0..100).rev().collect();
+    ///
+    /// let err = v.try_stooge_sort_by_with_budget(1000, |a, b| a.cmp(b)).unwrap_err();
+    /// assert_eq!(err.reason(), InterruptReason::BudgetExhausted);
+    /// assert_eq!(err.comparisons(), 1000);
+    /// assert_eq!(err.total_comparisons(), 265_720);
+    ///
+    /// let mut v = [-5, 4, 1, -3, 2];
+    /// v.try_stooge_sort_by_with_budget(1000, |a, b| a.cmp(b)).unwrap();
+    /// assert!(v == [-5, -3, 1, 2, 4]);
+    /// ```
+    fn try_stooge_sort_by_with_budget<F>(
+        &mut self,
+        max_comparisons: u64,
+        compare: F,
+    ) -> Result<(), Interrupted>
+    where
+        F: FnMut(&T, &T) -> Ordering;
+    /// Sorts the slice using stooge sort with a comparator function, giving up as soon
+    /// as `cancel` is set.
+    ///
+    /// `cancel` is checked before every comparison, so it can be set from another
+    /// thread (or from the comparator) to stop the sort. If it's set before the sort
+    /// finishes, an [`Interrupted`] error says how far it got, and the slice is left
+    /// partially sorted (but is still a permutation of the input). Otherwise, the
+    /// result is the same as [`stooge_sort_by`](Stooge::stooge_sort_by).
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use stoogesort::{InterruptReason, Stooge};
+    ///
+    /// let cancel = AtomicBool::new(false);
+    /// let mut comparisons = 0;
+    /// let mut v: Vec<u32> = (0..100).rev().collect();
+    ///
+    /// let err = v
+    ///     .try_stooge_sort_by_with_cancel(&cancel, |a, b| {
+    ///         comparisons += 1;
+    ///         if comparisons == 10 {
+    ///             cancel.store(true, Ordering::Relaxed);
+    ///         }
+    ///         a.cmp(b)
+    ///     })
+    ///     .unwrap_err();
+    /// assert_eq!(err.reason(), InterruptReason::Cancelled);
+    /// assert_eq!(err.comparisons(), 10);
+    /// ```
+    fn try_stooge_sort_by_with_cancel<F>(
+        &mut self,
+        cancel: &AtomicBool,
+        compare: F,
+    ) -> Result<(), Interrupted>
+    where
+        F: FnMut(&T, &T) -> Ordering;
+    /// Sorts the slice using stooge sort with a fallible comparator function.
+    ///
+    /// This sort is unstable, has worst-case
+    /// time complexity of O(n^(log(3)/log(1.5)))
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// The sort stops at the first error returned by the comparator, and returns it.
+    /// The slice is then left partially sorted (but is still a permutation of the
+    /// input). Otherwise, the result is the same as
+    /// [`stooge_sort_by`](Stooge::stooge_sort_by), and the comparator must likewise
+    /// define a total ordering.
+    ///
+    /// ```
+    /// use stoogesort::Stooge;
+    /// let mut floats = [5f64, 4.0, 1.0, 3.0, 2.0];
+    /// let result = floats.try_stooge_sort_by(|a, b| a.partial_cmp(b).ok_or("NaN"));
+    /// assert_eq!(result, Ok(()));
+    /// assert_eq!(floats, [1.0, 2.0, 3.0, 4.0, 5.0]);
+    ///
+    /// let mut floats = [5f64, 4.0, f64::NAN, 3.0, 2.0];
+    /// let result = floats.try_stooge_sort_by(|a, b| a.partial_cmp(b).ok_or("NaN"));
+    /// assert_eq!(result, Err("NaN"));
+    /// ```
+    fn try_stooge_sort_by<E, F>(&mut self, compare: F) -> Result<(), E>
+    where
+        F: FnMut(&T, &T) -> Result<Ordering, E>;
+    /// Sorts the slice using stooge sort with a fallible key extraction function.
+    ///
+    /// This sort is unstable, has worst-case
+    /// time complexity of O(n^(log(3)/log(1.5)) * m)
+    /// ≈ O(n^2.7095 * m), where the key function is O(m),
+    /// and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// The sort stops at the first error returned by the key function, and returns it.
+    /// The slice is then left partially sorted (but is still a permutation of the
+    /// input). Otherwise, the result is the same as
+    /// [`stooge_sort_by_key`](Stooge::stooge_sort_by_key).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stoogesort::Stooge;
+    /// let mut s = ["foo_1", "bar_0", "quux_2"];
+    /// let result = s.try_stooge_sort_by_key(|t| t.rsplit('_').next().unwrap().parse::<i64>());
+    /// assert!(result.is_ok());
+    /// assert_eq!(s, ["bar_0", "foo_1", "quux_2"]);
+    ///
+    /// let mut s = ["foo_1", "bar_zero", "quux_2"];
+    /// let result = s.try_stooge_sort_by_key(|t| t.rsplit('_').next().unwrap().parse::<i64>());
+    /// assert!(result.is_err());
+    /// ```
+    fn try_stooge_sort_by_key<E, F, K>(&mut self, f: F) -> Result<(), E>
+    where
+        F: FnMut(&T) -> Result<K, E>,
+        K: Ord;
+    /// Sorts the slice of [`PartialOrd`] elements using stooge sort, dealing with
+    /// incomparable elements according to `policy`.
+    ///
+    /// This sort is unstable, has worst-case
+    /// time complexity of O(n^(log(3)/log(1.5)))
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// An element is incomparable if it can't even be compared with itself, like a NaN.
+    /// See [`PartialPolicy`] for what happens to them. With
+    /// [`PartialPolicy::Error`], this returns an [`IncomparableError`] as soon as any
+    /// comparison fails, leaving the slice partially sorted (but still a permutation
+    /// of the input); with the other policies, it always succeeds.
+    ///
+    /// ```
+    /// use stoogesort::{PartialPolicy, Stooge};
+    /// let mut floats = [0.1, f64::NAN, 1.0, -1.6];
+    ///
+    /// floats.stooge_sort_partial(PartialPolicy::IncomparableLast).unwrap();
+    /// assert_eq!(floats[..3], [-1.6, 0.1, 1.0]);
+    /// assert!(floats[3].is_nan());
+    ///
+    /// assert!(floats.stooge_sort_partial(PartialPolicy::Error).is_err());
+    /// ```
+    fn stooge_sort_partial(&mut self, policy: PartialPolicy) -> Result<(), IncomparableError>
+    where
+        T: PartialOrd;
+    /// Sorts the elements of the slice within `range` using stooge sort, leaving the rest
+    /// alone.
+    ///
+    /// This sort is unstable, has worst-case
+    /// time complexity of O(k^(log(3)/log(1.5)))
+    /// ≈ O(k^2.7095), where k is the length of the range,
+    /// and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// This is equivalent to `self[range].stooge_sort()`, except that an invalid range is
+    /// reported as a [`RangeError`] instead of a panic.
+    ///
+    /// ```
+    /// use stoogesort::{RangeError, Stooge};
+    /// let mut v = [5, 4, 3, 2, 1];
+    ///
+    /// v.stooge_sort_range(1..4).unwrap();
+    /// assert!(v == [5, 2, 3, 4, 1]);
+    ///
+    /// assert_eq!(
+    ///     v.stooge_sort_range(3..=5),
+    ///     Err(RangeError::EndOutOfBounds { end: 6, len: 5 })
+    /// );
+    /// ```
+    fn stooge_sort_range<R>(&mut self, range: R) -> Result<(), RangeError>
+    where
+        T: Ord,
+        R: RangeBounds<usize>;
+    /// Sorts the elements of the slice within `range` using stooge sort with a comparator
+    /// function, leaving the rest alone.
+    ///
+    /// This sort is unstable, has worst-case
+    /// time complexity of O(k^(log(3)/log(1.5)))
+    /// ≈ O(k^2.7095), where k is the length of the range,
+    /// and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// This is equivalent to `self[range].stooge_sort_by(compare)`, except that an invalid
+    /// range is reported as a [`RangeError`] instead of a panic.
+    ///
+    /// ```
+    /// use stoogesort::Stooge;
+    /// let mut floats = [5f64, 4.0, 1.0, 3.0, 2.0];
+    /// floats.stooge_sort_range_by(..3, |a, b| a.partial_cmp(b).unwrap()).unwrap();
+    /// assert_eq!(floats, [1.0, 4.0, 5.0, 3.0, 2.0]);
+    /// ```
+    fn stooge_sort_range_by<R, F>(&mut self, range: R, compare: F) -> Result<(), RangeError>
+    where
+        R: RangeBounds<usize>,
+        F: FnMut(&T, &T) -> Ordering;
+    /// Sorts the elements of the slice within `range` using stooge sort with a key
+    /// extraction function, leaving the rest alone.
+    ///
+    /// This sort is unstable, has worst-case
+    /// time complexity of O(k^(log(3)/log(1.5)) * m)
+    /// ≈ O(k^2.7095 * m), where k is the length of the range and the key function is O(m),
+    /// and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// This is equivalent to `self[range].stooge_sort_by_key(f)`, except that an invalid
+    /// range is reported as a [`RangeError`] instead of a panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stoogesort::Stooge;
+    /// let mut v = [-5i32, 4, 1, -3, 2];
+    ///
+    /// v.stooge_sort_range_by_key(2.., |k| k.abs()).unwrap();
+    /// assert!(v == [-5, 4, 1, 2, -3]);
+    /// ```
+    fn stooge_sort_range_by_key<R, F, K>(&mut self, range: R, f: F) -> Result<(), RangeError>
+    where
+        R: RangeBounds<usize>,
+        F: FnMut(&T) -> K,
+        K: Ord;
 }
 
 impl<T> Stooge<T> for [T] {
@@ -97,25 +466,316 @@ impl<T> Stooge<T> for [T] {
             });
         }
     }
+    fn stooge_sort_with_stats(&mut self) -> StoogeStats
+    where
+        T: Ord,
+    {
+        self.stooge_sort_by_with_stats(T::cmp)
+    }
+    fn stooge_sort_by_with_stats<F>(&mut self, mut compare: F) -> StoogeStats
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut stats = StoogeStats::default();
+        if self.is_empty() || self.len() == 1 {
+        } else {
+            stooge_sort_observed(
+                self,
+                0,
+                self.len() - 1,
+                &mut |a, b| compare(a, b) == Ordering::Less,
+                &mut stats,
+            );
+        }
+        stats
+    }
+    fn stooge_sort_observed<O>(&mut self, observer: &mut O)
+    where
+        T: Ord,
+        O: StoogeObserver + ?Sized,
+    {
+        if self.is_empty() || self.len() == 1 {
+        } else {
+            stooge_sort_observed(self, 0, self.len() - 1, &mut T::lt, observer);
+        }
+    }
+    fn stooge_sort_by_observed<F, O>(&mut self, mut compare: F, observer: &mut O)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        O: StoogeObserver + ?Sized,
+    {
+        if self.is_empty() || self.len() == 1 {
+        } else {
+            stooge_sort_observed(
+                self,
+                0,
+                self.len() - 1,
+                &mut |a, b| compare(a, b) == Ordering::Less,
+                observer,
+            );
+        }
+    }
+    fn try_stooge_sort_by_with_budget<F>(
+        &mut self,
+        max_comparisons: u64,
+        mut compare: F,
+    ) -> Result<(), Interrupted>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.is_empty() || self.len() == 1 {
+            Ok(())
+        } else {
+            interrupt::stooge_sort_until(
+                self,
+                0,
+                self.len() - 1,
+                &mut |a, b| compare(a, b) == Ordering::Less,
+                &mut |comparisons| {
+                    if comparisons >= max_comparisons {
+                        Some(InterruptReason::BudgetExhausted)
+                    } else {
+                        None
+                    }
+                },
+            )
+        }
+    }
+    fn try_stooge_sort_by_with_cancel<F>(
+        &mut self,
+        cancel: &AtomicBool,
+        mut compare: F,
+    ) -> Result<(), Interrupted>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.is_empty() || self.len() == 1 {
+            Ok(())
+        } else {
+            interrupt::stooge_sort_until(
+                self,
+                0,
+                self.len() - 1,
+                &mut |a, b| compare(a, b) == Ordering::Less,
+                &mut |_| {
+                    if cancel.load(core::sync::atomic::Ordering::Relaxed) {
+                        Some(InterruptReason::Cancelled)
+                    } else {
+                        None
+                    }
+                },
+            )
+        }
+    }
+    fn try_stooge_sort_by<E, F>(&mut self, mut compare: F) -> Result<(), E>
+    where
+        F: FnMut(&T, &T) -> Result<Ordering, E>,
+    {
+        if self.is_empty() || self.len() == 1 {
+            Ok(())
+        } else {
+            try_stooge_sort(self, 0, self.len() - 1, &mut |a, b| {
+                Ok(compare(a, b)? == Ordering::Less)
+            })
+        }
+    }
+    fn try_stooge_sort_by_key<E, F, K>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&T) -> Result<K, E>,
+        K: Ord,
+    {
+        if self.is_empty() || self.len() == 1 {
+            Ok(())
+        } else {
+            try_stooge_sort(self, 0, self.len() - 1, &mut |a, b| Ok(f(a)?.lt(&f(b)?)))
+        }
+    }
+    fn stooge_sort_partial(&mut self, policy: PartialPolicy) -> Result<(), IncomparableError>
+    where
+        T: PartialOrd,
+    {
+        self.try_stooge_sort_by(|a, b| policy.compare(a, b))
+    }
+    fn stooge_sort_range<R>(&mut self, range: R) -> Result<(), RangeError>
+    where
+        T: Ord,
+        R: RangeBounds<usize>,
+    {
+        let range = range::check(range, self.len())?;
+        if range.len() > 1 {
+            stooge_sort(self, range.start, range.end - 1, &mut T::lt);
+        }
+        Ok(())
+    }
+    fn stooge_sort_range_by<R, F>(&mut self, range: R, mut compare: F) -> Result<(), RangeError>
+    where
+        R: RangeBounds<usize>,
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let range = range::check(range, self.len())?;
+        if range.len() > 1 {
+            stooge_sort(self, range.start, range.end - 1, &mut |a, b| {
+                compare(a, b) == Ordering::Less
+            });
+        }
+        Ok(())
+    }
+    fn stooge_sort_range_by_key<R, F, K>(&mut self, range: R, mut f: F) -> Result<(), RangeError>
+    where
+        R: RangeBounds<usize>,
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        let range = range::check(range, self.len())?;
+        if range.len() > 1 {
+            stooge_sort(self, range.start, range.end - 1, &mut |a, b| f(a).lt(&f(b)));
+        }
+        Ok(())
+    }
 }
 
+/// Sorts `v[left..=right]` without recursing.
 fn stooge_sort<T, F>(v: &mut [T], left: usize, right: usize, is_less: &mut F)
 where
     F: FnMut(&T, &T) -> bool,
 {
-    if !is_less(&v[left], &v[right]) {
-        v.swap(left, right);
+    stooge_sort_observed(v, left, right, is_less, &mut ());
+}
+
+/// Sorts `v[left..=right]` without recursing, reporting what it does to `observer`.
+fn stooge_sort_observed<T, F, O>(
+    v: &mut [T],
+    left: usize,
+    right: usize,
+    is_less: &mut F,
+    observer: &mut O,
+) where
+    F: FnMut(&T, &T) -> bool,
+    O: StoogeObserver + ?Sized,
+{
+    let mut work = WorkStack::new(left, right);
+
+    while let Some((left, right)) = work.next(observer) {
+        let less = is_less(&v[left], &v[right]);
+        observer.on_compare(left, right, less);
+        if !less {
+            v.swap(left, right);
+            observer.on_swap(left, right);
+        }
     }
+}
+
+/// Sorts `v[left..=right]` without recursing, stopping at the first error from `is_less`.
+fn try_stooge_sort<T, E, F>(
+    v: &mut [T],
+    left: usize,
+    right: usize,
+    is_less: &mut F,
+) -> Result<(), E>
+where
+    F: FnMut(&T, &T) -> Result<bool, E>,
+{
+    let mut work = WorkStack::new(left, right);
 
-    if (right - left + 1) > 2 {
-        let third = (right - left + 1) / 3;
-        stooge_sort(v, left, right - third, is_less);
-        stooge_sort(v, left + third, right, is_less);
-        stooge_sort(v, left, right - third, is_less);
+    while let Some((left, right)) = work.next(&mut ()) {
+        if !is_less(&v[left], &v[right])? {
+            v.swap(left, right);
+        }
+    }
+    Ok(())
+}
+
+/// The most frames a [`WorkStack`] can ever need to hold.
+///
+/// Each level of recursion leaves at most one frame on the stack, plus the two pushed by
+/// the call being started. Each level shrinks the range to at most two thirds of its
+/// length (rounded up), so even `usize::MAX` elements take fewer than `2 * usize::BITS`
+/// levels to get down to 2.
+const STACK_CAPACITY: usize = 2 * usize::BITS as usize;
+
+/// The pending work of a stooge sort, kept in a fixed-size array instead of on the
+/// call stack.
+///
+/// Each frame on the stack is `(left, right, phase)`, where `phase` counts how
+/// many of the three recursive sorts of `v[left..=right]` have already been started
+/// (a range of 2 elements skips straight to 3). The frames are visited in exactly the
+/// order the recursive formulation would visit them, so the two produce identical
+/// results. Every frame below the one being started belongs to an ancestor call, so
+/// the length of the stack is the recursion depth.
+struct WorkStack {
+    frames: [(usize, usize, u8); STACK_CAPACITY],
+    len: usize,
+}
+
+impl WorkStack {
+    /// Work for sorting `v[left..=right]`, where `left < right`.
+    fn new(left: usize, right: usize) -> Self {
+        let mut work = WorkStack::empty();
+        work.push((left, right, 0));
+        work
+    }
+
+    /// No work at all, for slices too short to need sorting.
+    fn empty() -> Self {
+        WorkStack {
+            frames: [(0, 0, 0); STACK_CAPACITY],
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, frame: (usize, usize, u8)) {
+        self.frames[self.len] = frame;
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(usize, usize, u8)> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            Some(self.frames[self.len])
+        }
+    }
+
+    /// Advances to the next call, returning the pair of indices it compare-exchanges,
+    /// or `None` once the sort is finished.
+    fn next<O>(&mut self, observer: &mut O) -> Option<(usize, usize)>
+    where
+        O: StoogeObserver + ?Sized,
+    {
+        while let Some((left, right, phase)) = self.pop() {
+            let third = (right - left + 1) / 3;
+            match phase {
+                0 => {
+                    observer.on_enter(left, right, self.len + 1);
+                    if (right - left + 1) > 2 {
+                        self.push((left, right, 1));
+                        self.push((left, right - third, 0));
+                    } else {
+                        self.push((left, right, 3));
+                    }
+                    // The frames just pushed aren't visited until the caller comes
+                    // back, by which point it has compare-exchanged the endpoints.
+                    return Some((left, right));
+                }
+                1 => {
+                    self.push((left, right, 2));
+                    self.push((left + third, right, 0));
+                }
+                2 => {
+                    self.push((left, right, 3));
+                    self.push((left, right - third, 0));
+                }
+                _ => observer.on_exit(left, right, self.len + 1),
+            }
+        }
+        None
     }
 }
 
 #[cfg(test)]
+// Some of the oldest tests borrow their distributions, which newer Clippy frowns on.
+#[allow(clippy::needless_borrows_for_generic_args)]
 mod tests {
     use super::*;
     use rand::{distributions::Uniform, Rng};
@@ -187,6 +847,185 @@ mod tests {
         assert_eq!(test, reference);
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn cached_key_matches_by_key() {
+        let mut rng = rand::thread_rng();
+        let range = Uniform::new(-100, 100);
+        let mut test: Vec<i64> = (1..=200).map(|_| rng.sample(range)).collect();
+        let mut reference = test.clone();
+
+        test.stooge_sort_by_cached_key(|n| n.abs());
+        reference.stooge_sort_by_key(|n| n.abs());
+
+        assert_eq!(test, reference);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn cached_key_calls_once() {
+        let mut calls = 0;
+        let mut test = ["foo_1", "bar_0", "quux_2", "baz_10", "qux_3"];
+        test.stooge_sort_by_cached_key(|t| {
+            calls += 1;
+            t.rsplit('_').next().unwrap().parse::<i64>().unwrap()
+        });
+        assert_eq!(test, ["bar_0", "foo_1", "quux_2", "qux_3", "baz_10"]);
+        assert_eq!(calls, 5);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[derive(Debug, PartialEq)]
+    struct Record {
+        group: u8,
+        id: usize,
+    }
+
+    #[cfg(feature = "alloc")]
+    fn records(len: usize) -> Vec<Record> {
+        let mut rng = rand::thread_rng();
+        let range = Uniform::new(0, 4);
+        (0..len)
+            .map(|id| Record {
+                group: rng.sample(range),
+                id,
+            })
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn stable_by_keeps_equal_records_in_order() {
+        let mut test = records(100);
+        test.stooge_sort_stable_by(|a, b| a.group.cmp(&b.group));
+
+        for pair in test.windows(2) {
+            assert!(pair[0].group <= pair[1].group);
+            if pair[0].group == pair[1].group {
+                assert!(pair[0].id < pair[1].id);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn stable_by_key_matches_std() {
+        let mut test = records(100);
+        let mut reference: Vec<Record> = test
+            .iter()
+            .map(|r| Record {
+                group: r.group,
+                id: r.id,
+            })
+            .collect();
+
+        test.stooge_sort_stable_by_key(|r| r.group);
+        reference.sort_by_key(|r| r.group);
+
+        assert_eq!(test, reference);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn unstable_reorders_equal_elements() {
+        let mut test = [(0, 'a'), (1, 'b'), (1, 'c')];
+        test.stooge_sort_by_key(|t| t.0);
+        assert_eq!(test, [(0, 'a'), (1, 'c'), (1, 'b')]);
+
+        let mut test = [(0, 'a'), (1, 'b'), (1, 'c')];
+        test.stooge_sort_stable_by_key(|t| t.0);
+        assert_eq!(test, [(0, 'a'), (1, 'b'), (1, 'c')]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn stable_of_ord() {
+        let mut test = [3, 1, 2, 1, 0].to_vec();
+        test.stooge_sort_stable();
+        assert_eq!(test, [0, 1, 1, 2, 3]);
+    }
+
+    fn recursive_stooge_sort<T, F>(v: &mut [T], left: usize, right: usize, is_less: &mut F)
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        if !is_less(&v[left], &v[right]) {
+            v.swap(left, right);
+        }
+
+        if (right - left + 1) > 2 {
+            let third = (right - left + 1) / 3;
+            recursive_stooge_sort(v, left, right - third, is_less);
+            recursive_stooge_sort(v, left + third, right, is_less);
+            recursive_stooge_sort(v, left, right - third, is_less);
+        }
+    }
+
+    #[test]
+    fn iterative_matches_recursive() {
+        crate::test_util::assert_sorts_like_stooge_sort(100, |mut test| {
+            if test.len() > 1 {
+                let right = test.len() - 1;
+                recursive_stooge_sort(&mut test, 0, right, &mut |a, b| a.0 < b.0);
+            }
+            test
+        });
+    }
+
+    #[test]
+    fn try_sort_matches_infallible() {
+        let mut rng = rand::thread_rng();
+        let range = Uniform::new(-100, 100);
+        let mut test: Vec<i64> = (1..=100).map(|_| rng.sample(range)).collect();
+        let mut reference = test.clone();
+
+        assert_eq!(
+            test.try_stooge_sort_by_key(|n| Ok::<_, ()>(n.abs())),
+            Ok(())
+        );
+        reference.stooge_sort_by_key(|n| n.abs());
+
+        assert_eq!(test, reference);
+    }
+
+    #[test]
+    fn try_sort_stops_at_first_error() {
+        let mut calls = 0;
+        let mut test: Vec<i64> = (0..20).rev().collect();
+        let result = test.try_stooge_sort_by(|a, b| {
+            calls += 1;
+            if calls == 7 {
+                Err(calls)
+            } else {
+                Ok(a.cmp(b))
+            }
+        });
+
+        assert_eq!(result, Err(7));
+        assert_eq!(calls, 7);
+        test.sort();
+        assert_eq!(test, (0..20).collect::<Vec<i64>>());
+    }
+
+    #[test]
+    fn stack_capacity_is_enough() {
+        // How many frames sorting `len` elements needs: one for each level of
+        // recursion, plus one more for the deepest call's children.
+        fn frames(mut len: usize) -> usize {
+            let mut frames = 2;
+            while len > 2 {
+                len -= len / 3;
+                frames += 1;
+            }
+            frames
+        }
+
+        assert!(frames(usize::MAX) <= STACK_CAPACITY);
+        // The same must hold on 32- and 16-bit targets.
+        assert!(frames(u32::MAX as usize) <= 2 * 32);
+        assert!(frames(u16::MAX as usize) <= 2 * 16);
+    }
+
     #[test]
     fn vec_of_char() {
         let mut test: Vec<char> = "1312".chars().collect();
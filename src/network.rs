@@ -0,0 +1,263 @@
+//! Stooge sort as a sorting network.
+
+#[cfg(feature = "alloc")]
+use crate::WorkStack;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// Returns the compare-exchanges stooge sort makes on a slice of length `n`, in order.
+///
+/// Stooge sort is data-oblivious: which pairs of indices it compares never depends on
+/// what's in the slice, only on how long it is. So this is a sorting network, which can
+/// be reused with [`apply_network`], checked, or built in hardware. Each pair `(i, j)`
+/// has `i < j`.
+///
+/// There are as many pairs as [`StoogeStats::comparisons`](crate::StoogeStats::comparisons)
+/// for a slice of length `n`, which grows like n^2.7095, so this gets big quickly.
+///
+/// ```
+/// use stoogesort::network;
+///
+/// assert_eq!(network(3), [(0, 2), (0, 1), (1, 2), (0, 1)]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn network(n: usize) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    if n < 2 {
+        return pairs;
+    }
+
+    let mut work = WorkStack::new(0, n - 1);
+    while let Some(pair) = work.next(&mut ()) {
+        pairs.push(pair);
+    }
+    pairs
+}
+
+/// Applies a sorting network to `v`.
+///
+/// For each pair `(i, j)`, in order, swaps `v[i]` and `v[j]` unless `v[i] < v[j]`. This
+/// is the rule stooge sort uses, so applying `network(v.len())` (with the `alloc`
+/// feature) gives exactly the same result as [`stooge_sort`](crate::Stooge::stooge_sort).
+///
+/// # Panics
+///
+/// Panics if any index is out of bounds.
+///
+/// ```
+/// use stoogesort::apply_network;
+///
+/// // The same as `network(3)`.
+/// let schedule = [(0, 2), (0, 1), (1, 2), (0, 1)];
+/// let mut a = [3, 2, 1];
+/// let mut b = [0, -1, 1];
+///
+/// apply_network(&mut a, &schedule);
+/// apply_network(&mut b, &schedule);
+/// assert_eq!(a, [1, 2, 3]);
+/// assert_eq!(b, [-1, 0, 1]);
+/// ```
+pub fn apply_network<T: Ord>(v: &mut [T], network: &[(usize, usize)]) {
+    apply_network_by(v, network, T::cmp);
+}
+
+/// Applies a sorting network to `v` with a comparator function.
+///
+/// For each pair `(i, j)`, in order, swaps `v[i]` and `v[j]` unless `v[i]` compares
+/// less than `v[j]`, as with [`apply_network`].
+///
+/// # Panics
+///
+/// Panics if any index is out of bounds.
+pub fn apply_network_by<T, F>(v: &mut [T], network: &[(usize, usize)], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    for &(i, j) in network {
+        if compare(&v[i], &v[j]) != Ordering::Less {
+            v.swap(i, j);
+        }
+    }
+}
+
+/// Sorts every row using stooge sort, working out the compare-exchanges just once.
+///
+/// This gives the same result as calling [`stooge_sort`](crate::Stooge::stooge_sort)
+/// on each row, but replays a precomputed [`network(N)`](network) instead of walking
+/// the recursion again for every row, which adds up for lots of short rows.
+///
+/// This sort is unstable, has worst-case
+/// time complexity of O(m * N^(log(3)/log(1.5)))
+/// ≈ O(m * N^2.7095) for m rows, and doesn't recurse (see [Recursion](crate#recursion)).
+/// The network takes O(N^2.7095) memory.
+///
+/// ```
+/// use stoogesort::stooge_sort_batch;
+/// let mut rows = [[3, 1, 2], [9, 8, 7], [4, 6, 5]];
+///
+/// stooge_sort_batch(&mut rows);
+/// assert_eq!(rows, [[1, 2, 3], [7, 8, 9], [4, 5, 6]]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn stooge_sort_batch<T: Ord, const N: usize>(rows: &mut [[T; N]]) {
+    stooge_sort_batch_by(rows, T::cmp);
+}
+
+/// Sorts every row using stooge sort with a comparator function, working out the
+/// compare-exchanges just once.
+///
+/// This sort is unstable, has worst-case
+/// time complexity of O(m * N^(log(3)/log(1.5)))
+/// ≈ O(m * N^2.7095) for m rows, and doesn't recurse (see [Recursion](crate#recursion)).
+///
+/// The comparator function must define a total ordering, as with
+/// [`stooge_sort_by`](crate::Stooge::stooge_sort_by).
+#[cfg(feature = "alloc")]
+pub fn stooge_sort_batch_by<T, F, const N: usize>(rows: &mut [[T; N]], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let schedule = network(N);
+    for row in rows {
+        apply_network_by(row, &schedule, &mut compare);
+    }
+}
+
+/// Sorts every `chunk_len`-element chunk of `v` using stooge sort, working out the
+/// compare-exchanges just once.
+///
+/// This is [`stooge_sort_batch`] for rows laid out end to end in one slice, when the
+/// row length isn't known at compile time.
+///
+/// This sort is unstable, has worst-case
+/// time complexity of O(m * k^(log(3)/log(1.5)))
+/// ≈ O(m * k^2.7095) for m chunks of length k, and doesn't recurse (see
+/// [Recursion](crate#recursion)).
+///
+/// # Panics
+///
+/// Panics if `chunk_len` is 0 or doesn't divide the length of `v`.
+///
+/// ```
+/// use stoogesort::stooge_sort_chunks;
+/// let mut readings = [3, 1, 2, 9, 8, 7, 4, 6, 5];
+///
+/// stooge_sort_chunks(&mut readings, 3);
+/// assert_eq!(readings, [1, 2, 3, 7, 8, 9, 4, 5, 6]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn stooge_sort_chunks<T: Ord>(v: &mut [T], chunk_len: usize) {
+    stooge_sort_chunks_by(v, chunk_len, T::cmp);
+}
+
+/// Sorts every `chunk_len`-element chunk of `v` using stooge sort with a comparator
+/// function, working out the compare-exchanges just once.
+///
+/// This sort is unstable, has worst-case
+/// time complexity of O(m * k^(log(3)/log(1.5)))
+/// ≈ O(m * k^2.7095) for m chunks of length k, and doesn't recurse (see
+/// [Recursion](crate#recursion)).
+///
+/// The comparator function must define a total ordering, as with
+/// [`stooge_sort_by`](crate::Stooge::stooge_sort_by).
+///
+/// # Panics
+///
+/// Panics if `chunk_len` is 0 or doesn't divide the length of `v`.
+#[cfg(feature = "alloc")]
+pub fn stooge_sort_chunks_by<T, F>(v: &mut [T], chunk_len: usize, mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    assert!(chunk_len != 0, "chunk length is 0");
+    assert!(
+        v.len() % chunk_len == 0,
+        "slice length {} isn't a multiple of the chunk length {}",
+        v.len(),
+        chunk_len
+    );
+
+    let schedule = network(chunk_len);
+    for chunk in v.chunks_exact_mut(chunk_len) {
+        apply_network_by(chunk, &schedule, &mut compare);
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::Stooge;
+
+    #[test]
+    fn matches_stooge_sort() {
+        crate::test_util::assert_sorts_like_stooge_sort(40, |mut v| {
+            let schedule = network(v.len());
+            apply_network_by(&mut v, &schedule, |a, b| a.0.cmp(&b.0));
+            v
+        });
+    }
+
+    #[test]
+    fn one_pair_per_comparison() {
+        for n in 0..40 {
+            let schedule = network(n);
+            let stats = vec![0_u8; n].stooge_sort_with_stats();
+            assert_eq!(schedule.len() as u64, stats.comparisons);
+            assert!(schedule.iter().all(|&(i, j)| i < j && j < n));
+        }
+    }
+
+    #[test]
+    fn sorts_every_binary_input() {
+        // The 0-1 principle: a network that sorts every sequence of 0s and 1s sorts
+        // everything.
+        for n in 0..12 {
+            let schedule = network(n);
+            for bits in 0..1u32 << n {
+                let mut v: Vec<u32> = (0..n).map(|i| bits >> i & 1).collect();
+                apply_network(&mut v, &schedule);
+                assert!(v.windows(2).all(|w| w[0] <= w[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn batch_matches_stooge_sort() {
+        let mut flat = crate::test_util::tagged_pairs(13 * 20);
+        let mut rows = [[(0_u8, 0_usize); 13]; 20];
+        for (pair, &input) in rows.iter_mut().flatten().zip(&flat) {
+            *pair = input;
+        }
+        let mut expected = rows;
+
+        stooge_sort_batch_by(&mut rows, |a, b| a.0.cmp(&b.0));
+        stooge_sort_chunks_by(&mut flat, 13, |a, b| a.0.cmp(&b.0));
+        for row in expected.iter_mut() {
+            row.stooge_sort_by_key(|p| p.0);
+        }
+
+        assert_eq!(rows, expected);
+        assert!(flat.iter().eq(expected.iter().flatten()));
+    }
+
+    #[test]
+    fn batch_of_nothing() {
+        let mut rows: [[u8; 0]; 3] = [[]; 3];
+        stooge_sort_batch(&mut rows);
+        stooge_sort_batch::<u8, 4>(&mut []);
+        stooge_sort_chunks::<u8>(&mut [], 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't a multiple of the chunk length")]
+    fn ragged_chunks() {
+        stooge_sort_chunks(&mut [3, 2, 1, 0, 4], 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        apply_network(&mut [1, 0], &[(0, 2)]);
+    }
+}
@@ -0,0 +1,158 @@
+//! Watching a sort as it happens.
+
+/// Callbacks for every step of a stooge sort.
+///
+/// Pass an observer to [`stooge_sort_observed`](crate::Stooge::stooge_sort_observed) or
+/// [`stooge_sort_by_observed`](crate::Stooge::stooge_sort_by_observed) to build
+/// visualizers, loggers, auditors and the like. Every method has a default
+/// implementation that does nothing, so implement only the ones you need.
+///
+/// Each call to sort `v[left..=right]`
+///
+/// 1. is announced with [`on_enter`](StoogeObserver::on_enter),
+/// 2. compares `v[left]` and `v[right]`, reported by [`on_compare`](StoogeObserver::on_compare),
+/// 3. swaps them unless `v[left] < v[right]`, reported by [`on_swap`](StoogeObserver::on_swap),
+/// 4. if the range has more than 2 elements, sorts its first two thirds, its last two
+///    thirds and its first two thirds again, and finally
+/// 5. is wrapped up with [`on_exit`](StoogeObserver::on_exit).
+///
+/// The sort doesn't actually recurse, but the calls are reported as though it did.
+/// Indices are relative to the start of the slice being sorted.
+///
+/// ```
+/// use stoogesort::{Stooge, StoogeObserver};
+///
+/// struct Indent(String);
+///
+/// impl StoogeObserver for Indent {
+///     fn on_enter(&mut self, left: usize, right: usize, depth: usize) {
+///         self.0 += &format!("{}{}..={}\n", "  ".repeat(depth - 1), left, right);
+///     }
+/// }
+///
+/// let mut v = [2, 0, 1];
+/// let mut trace = Indent(String::new());
+///
+/// v.stooge_sort_observed(&mut trace);
+/// assert_eq!(trace.0, "0..=2\n  0..=1\n  1..=2\n  0..=1\n");
+/// ```
+pub trait StoogeObserver {
+    /// A call sorting `v[left..=right]` has started, `depth` calls deep
+    /// (the outermost call is 1 deep).
+    fn on_enter(&mut self, _left: usize, _right: usize, _depth: usize) {}
+    /// `v[i]` and `v[j]` have been compared; `less` is whether `v[i] < v[j]`.
+    fn on_compare(&mut self, _i: usize, _j: usize, _less: bool) {}
+    /// `v[i]` and `v[j]` have been swapped.
+    fn on_swap(&mut self, _i: usize, _j: usize) {}
+    /// The call sorting `v[left..=right]`, `depth` calls deep, has finished.
+    fn on_exit(&mut self, _left: usize, _right: usize, _depth: usize) {}
+}
+
+/// Observes nothing.
+impl StoogeObserver for () {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Stooge, StoogeObserver};
+    use rand::{distributions::Uniform, Rng};
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Enter(usize, usize, usize),
+        Compare(usize, usize, bool),
+        Swap(usize, usize),
+        Exit(usize, usize, usize),
+    }
+
+    #[derive(Default)]
+    struct Recorder(Vec<Event>);
+
+    impl StoogeObserver for Recorder {
+        fn on_enter(&mut self, left: usize, right: usize, depth: usize) {
+            self.0.push(Event::Enter(left, right, depth));
+        }
+        fn on_compare(&mut self, i: usize, j: usize, less: bool) {
+            self.0.push(Event::Compare(i, j, less));
+        }
+        fn on_swap(&mut self, i: usize, j: usize) {
+            self.0.push(Event::Swap(i, j));
+        }
+        fn on_exit(&mut self, left: usize, right: usize, depth: usize) {
+            self.0.push(Event::Exit(left, right, depth));
+        }
+    }
+
+    #[test]
+    fn three_elements() {
+        use Event::*;
+
+        let mut test = [1, 2, 0];
+        let mut recorder = Recorder::default();
+        test.stooge_sort_observed(&mut recorder);
+
+        assert_eq!(test, [0, 1, 2]);
+        assert_eq!(
+            recorder.0,
+            [
+                Enter(0, 2, 1),
+                Compare(0, 2, false),
+                Swap(0, 2),
+                Enter(0, 1, 2),
+                Compare(0, 1, true),
+                Exit(0, 1, 2),
+                Enter(1, 2, 2),
+                Compare(1, 2, false),
+                Swap(1, 2),
+                Exit(1, 2, 2),
+                Enter(0, 1, 2),
+                Compare(0, 1, true),
+                Exit(0, 1, 2),
+                Exit(0, 2, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn replaying_swaps_sorts() {
+        let mut rng = rand::thread_rng();
+        let range = Uniform::new(-100, 100);
+        let mut test: Vec<i64> = (1..=100).map(|_| rng.sample(range)).collect();
+        let mut replay = test.clone();
+
+        let mut recorder = Recorder::default();
+        test.stooge_sort_by_observed(|a, b| b.cmp(a), &mut recorder);
+
+        for event in recorder.0 {
+            if let Event::Swap(i, j) = event {
+                replay.swap(i, j);
+            }
+        }
+        assert_eq!(test, replay);
+        assert!(test.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    #[test]
+    fn enters_and_exits_nest() {
+        let mut test: Vec<u32> = (0..40).rev().collect();
+        let mut recorder = Recorder::default();
+        test.stooge_sort_observed(&mut recorder);
+
+        let mut open = Vec::new();
+        for event in recorder.0 {
+            match event {
+                Event::Enter(left, right, depth) => {
+                    assert_eq!(depth, open.len() + 1);
+                    open.push((left, right));
+                }
+                Event::Exit(left, right, depth) => {
+                    assert_eq!(depth, open.len());
+                    assert_eq!(open.pop(), Some((left, right)));
+                }
+                Event::Compare(i, j, _) | Event::Swap(i, j) => {
+                    assert_eq!(open.last(), Some(&(i, j)));
+                }
+            }
+        }
+        assert!(open.is_empty());
+    }
+}
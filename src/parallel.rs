@@ -0,0 +1,243 @@
+//! Sorting with more than one thread.
+
+use crate::{Stooge, WorkStack};
+use std::cmp::Ordering;
+use std::hint;
+use std::num::NonZeroUsize;
+use std::ptr;
+use std::sync::atomic::{self, AtomicBool, AtomicUsize};
+use std::thread;
+
+/// Stooge sort spread across multiple threads.
+///
+/// Available with the `parallel` feature, which requires Rust 1.63 or later.
+///
+/// # Don't get your hopes up
+///
+/// Stooge sort compare-exchanges the endpoints of a range, then sorts the first two
+/// thirds, the last two thirds, and the first two thirds again. Each of those
+/// sorts overlaps the one before it, so there's precious little that can happen at
+/// the same time. The only compare-exchanges that can run in parallel are ones that
+/// touch different elements and aren't ordered by an earlier compare-exchange on
+/// either of their elements. For 400 elements, the longest chain of compare-exchanges
+/// that must happen one after the other is about 62% of the total, so no number of
+/// threads can make the sort more than about 1.6 times faster.
+///
+/// Coordinating the threads costs something for every comparison, so this is only
+/// worth it if the comparator is expensive. See `benches/parallel.rs`.
+///
+/// The result is exactly the same as that of the serial sort.
+pub trait StoogePar<T> {
+    /// Sorts the slice using stooge sort on as many threads as are available.
+    ///
+    /// This sort is unstable, has worst-case
+    /// time complexity of O(n^(log(3)/log(1.5)))
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// The result is the same as [`stooge_sort`](Stooge::stooge_sort).
+    ///
+    /// ```
+    /// use stoogesort::StoogePar;
+    /// let mut v = [-5, 4, 1, -3, 2];
+    ///
+    /// v.stooge_sort_par();
+    /// assert!(v == [-5, -3, 1, 2, 4]);
+    /// ```
+    fn stooge_sort_par(&mut self)
+    where
+        T: Ord;
+    /// Sorts the slice using stooge sort with a comparator function on as many
+    /// threads as are available.
+    ///
+    /// This sort is unstable, has worst-case
+    /// time complexity of O(n^(log(3)/log(1.5)))
+    /// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+    ///
+    /// The result is the same as [`stooge_sort_by`](Stooge::stooge_sort_by), and the
+    /// comparator must likewise define a total ordering. If it panics, the panic is
+    /// propagated once every thread has stopped, and the slice is left partially
+    /// sorted.
+    ///
+    /// ```
+    /// use stoogesort::StoogePar;
+    /// let mut floats = [5f64, 4.0, 1.0, 3.0, 2.0];
+    /// floats.stooge_sort_par_by(|a, b| a.partial_cmp(b).unwrap());
+    /// assert_eq!(floats, [1.0, 2.0, 3.0, 4.0, 5.0]);
+    /// ```
+    fn stooge_sort_par_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync;
+}
+
+impl<T: Send> StoogePar<T> for [T] {
+    fn stooge_sort_par(&mut self)
+    where
+        T: Ord,
+    {
+        self.stooge_sort_par_by(T::cmp);
+    }
+    fn stooge_sort_par_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        stooge_sort_par_by(self, threads, &compare);
+    }
+}
+
+/// A pointer to the slice being sorted, shared between the threads.
+///
+/// Each thread only ever touches the pair of elements it's compare-exchanging, and
+/// waits until every earlier compare-exchange on either of them is done first.
+struct SharedSlice<T>(*mut T);
+
+// SAFETY: sharing the pointer lets other threads reach the elements, so they must be
+// `Send`. They needn't be `Sync`, though: since compare-exchanges on an element wait
+// for the earlier ones to finish, no two threads ever hold the same element at once,
+// so it's only ever handed from one thread to the next, never shared.
+unsafe impl<T: Send> Sync for SharedSlice<T> {}
+
+/// Sorts `v` with `threads` threads.
+///
+/// Every thread walks through the whole (data-independent) sequence of
+/// compare-exchanges, counting how many touch each element, but only carries out every
+/// `threads`th one. Before it does, it waits for each element's count of finished
+/// compare-exchanges to catch up, so every element sees its compare-exchanges in the
+/// same order as it would in the serial sort. The earliest unfinished compare-exchange
+/// never waits on anything, so this can't deadlock.
+fn stooge_sort_par_by<T, F>(v: &mut [T], threads: usize, compare: &F)
+where
+    T: Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    if v.len() < 2 {
+        return;
+    }
+    if threads < 2 {
+        v.stooge_sort_by(compare);
+        return;
+    }
+
+    let len = v.len();
+    let finished: Vec<AtomicUsize> = (0..len).map(|_| AtomicUsize::new(0)).collect();
+    let poisoned = AtomicBool::new(false);
+    let slice = SharedSlice(v.as_mut_ptr());
+
+    thread::scope(|scope| {
+        for thread in 0..threads {
+            let (finished, poisoned, slice) = (&finished, &poisoned, &slice);
+            scope.spawn(move || {
+                let _guard = PoisonOnPanic(poisoned);
+                let mut started = vec![0; len];
+                let mut work = WorkStack::new(0, len - 1);
+                let mut index = 0;
+
+                while let Some((left, right)) = work.next(&mut ()) {
+                    let (before_left, before_right) = (started[left], started[right]);
+                    started[left] += 1;
+                    started[right] += 1;
+
+                    if index % threads == thread {
+                        if !wait_for(&finished[left], before_left, poisoned)
+                            || !wait_for(&finished[right], before_right, poisoned)
+                        {
+                            return;
+                        }
+
+                        // SAFETY: `left` and `right` are in bounds and distinct, and every
+                        // other compare-exchange involving either of them is either
+                        // finished (and its writes acquired by `wait_for`) or waiting for
+                        // this one to finish.
+                        unsafe {
+                            let a = slice.0.add(left);
+                            let b = slice.0.add(right);
+                            if compare(&*a, &*b) != Ordering::Less {
+                                ptr::swap(a, b);
+                            }
+                        }
+
+                        finished[left].store(before_left + 1, atomic::Ordering::Release);
+                        finished[right].store(before_right + 1, atomic::Ordering::Release);
+                    }
+                    index += 1;
+                }
+            });
+        }
+    });
+}
+
+/// Waits until `finished` reaches `expected`, returning `false` if another thread
+/// panicked in the meantime.
+fn wait_for(finished: &AtomicUsize, expected: usize, poisoned: &AtomicBool) -> bool {
+    let mut spins = 0;
+    while finished.load(atomic::Ordering::Acquire) != expected {
+        if poisoned.load(atomic::Ordering::Relaxed) {
+            return false;
+        }
+        if spins < 64 {
+            spins += 1;
+            hint::spin_loop();
+        } else {
+            thread::yield_now();
+        }
+    }
+    true
+}
+
+/// Tells the other threads to stop waiting if this one panics.
+struct PoisonOnPanic<'a>(&'a AtomicBool);
+
+impl Drop for PoisonOnPanic<'_> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            self.0.store(true, atomic::Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic;
+
+    #[test]
+    fn matches_serial() {
+        for &threads in &[2, 3, 4] {
+            crate::test_util::assert_sorts_like_stooge_sort(40, |mut test| {
+                stooge_sort_par_by(&mut test, threads, &|a, b| a.0.cmp(&b.0));
+                test
+            });
+        }
+    }
+
+    #[test]
+    fn sort_strings() {
+        let mut test = vec!["6502", "2650", "680x0", "Z80", "8080", "8086", "Z8000"]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<String>>();
+        test.stooge_sort_par();
+        assert_eq!(
+            test,
+            ["2650", "6502", "680x0", "8080", "8086", "Z80", "Z8000"]
+        );
+    }
+
+    #[test]
+    fn panic_propagates() {
+        let mut test: Vec<u32> = (0..30).rev().collect();
+        let calls = AtomicUsize::new(0);
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            stooge_sort_par_by(&mut test, 3, &|a: &u32, b: &u32| {
+                if calls.fetch_add(1, atomic::Ordering::Relaxed) == 100 {
+                    panic!("comparator failed");
+                }
+                a.cmp(b)
+            })
+        }));
+
+        assert!(result.is_err());
+        test.sort();
+        assert_eq!(test, (0..30).collect::<Vec<u32>>());
+    }
+}
@@ -0,0 +1,171 @@
+//! Sorting [`PartialOrd`] types without unwrapping.
+
+use core::cmp::Ordering;
+use core::fmt;
+
+/// What [`stooge_sort_partial`](crate::Stooge::stooge_sort_partial) does with
+/// incomparable elements.
+///
+/// An element is incomparable if it can't even be compared with itself (i.e.
+/// `x.partial_cmp(&x)` is `None`), like a NaN. Incomparable elements are all considered
+/// equal to each other.
+///
+/// Comparable elements that can't be compared with each other (say, two sets where
+/// neither contains the other) are considered equal by
+/// [`IncomparableFirst`](PartialPolicy::IncomparableFirst) and
+/// [`IncomparableLast`](PartialPolicy::IncomparableLast), so their order is unspecified.
+/// Use [`Error`](PartialPolicy::Error) to rule them out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartialPolicy {
+    /// Incomparable elements are less than everything else, so they end up first.
+    IncomparableFirst,
+    /// Incomparable elements are greater than everything else, so they end up last.
+    IncomparableLast,
+    /// Any failed comparison stops the sort with an [`IncomparableError`].
+    Error,
+}
+
+impl PartialPolicy {
+    /// Compares `a` and `b` as this policy dictates.
+    pub(crate) fn compare<T>(self, a: &T, b: &T) -> Result<Ordering, IncomparableError>
+    where
+        T: PartialOrd,
+    {
+        if let Some(ordering) = a.partial_cmp(b) {
+            return Ok(ordering);
+        }
+
+        let incomparable_first = match self {
+            PartialPolicy::IncomparableFirst => true,
+            PartialPolicy::IncomparableLast => false,
+            PartialPolicy::Error => return Err(IncomparableError),
+        };
+        let ordering = match (is_incomparable(a), is_incomparable(b)) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            _ => return Ok(Ordering::Equal),
+        };
+        Ok(if incomparable_first {
+            ordering
+        } else {
+            ordering.reverse()
+        })
+    }
+}
+
+fn is_incomparable<T>(x: &T) -> bool
+where
+    T: PartialOrd,
+{
+    x.partial_cmp(x).is_none()
+}
+
+/// The error returned by [`stooge_sort_partial`](crate::Stooge::stooge_sort_partial)
+/// with [`PartialPolicy::Error`] when two elements can't be compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IncomparableError;
+
+impl fmt::Display for IncomparableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("encountered a pair of incomparable elements")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IncomparableError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Stooge;
+    use rand::{distributions::Uniform, Rng};
+
+    fn floats_with_nans() -> Vec<f64> {
+        let mut rng = rand::thread_rng();
+        let range = Uniform::new(-1000.0, 1000.0);
+        let mut test: Vec<f64> = (1..=100).map(|_| rng.sample(range)).collect();
+        for i in (0..100).step_by(7) {
+            test[i] = f64::NAN;
+        }
+        test
+    }
+
+    #[test]
+    fn nans_first() {
+        let mut test = floats_with_nans();
+        test.stooge_sort_partial(PartialPolicy::IncomparableFirst)
+            .unwrap();
+
+        let nans = test.iter().take_while(|x| x.is_nan()).count();
+        assert_eq!(nans, 15);
+        assert!(test[nans..].windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn nans_last() {
+        let mut test = floats_with_nans();
+        test.stooge_sort_partial(PartialPolicy::IncomparableLast)
+            .unwrap();
+
+        let numbers = test.iter().take_while(|x| !x.is_nan()).count();
+        assert_eq!(numbers, 85);
+        assert!(test[numbers..].iter().all(|x| x.is_nan()));
+        assert!(test[..numbers].windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn error_on_nan() {
+        let mut test = floats_with_nans();
+        assert_eq!(
+            test.stooge_sort_partial(PartialPolicy::Error),
+            Err(IncomparableError)
+        );
+        assert_eq!(test.iter().filter(|x| x.is_nan()).count(), 15);
+    }
+
+    #[test]
+    fn no_nans_any_policy() {
+        let reference = [-1.6, 0.0, 0.1, 1.0];
+        for &policy in &[
+            PartialPolicy::IncomparableFirst,
+            PartialPolicy::IncomparableLast,
+            PartialPolicy::Error,
+        ] {
+            let mut test = [0.1, 0.0, 1.0, -1.6];
+            assert_eq!(test.stooge_sort_partial(policy), Ok(()));
+            assert_eq!(test, reference);
+        }
+    }
+
+    #[test]
+    fn mutually_incomparable_but_not_self_incomparable() {
+        // Neither is a NaN, so they're left alone rather than moved to the end.
+        #[derive(Debug, PartialEq)]
+        struct Set(u8);
+
+        impl PartialOrd for Set {
+            fn partial_cmp(&self, other: &Set) -> Option<Ordering> {
+                if self.0 == other.0 {
+                    Some(Ordering::Equal)
+                } else if self.0 & other.0 == self.0 {
+                    Some(Ordering::Less)
+                } else if self.0 & other.0 == other.0 {
+                    Some(Ordering::Greater)
+                } else {
+                    None
+                }
+            }
+        }
+
+        let mut test = [Set(0b11), Set(0b01), Set(0b10), Set(0b00)];
+        assert_eq!(
+            test.stooge_sort_partial(PartialPolicy::Error),
+            Err(IncomparableError)
+        );
+
+        let mut test = [Set(0b11), Set(0b00)];
+        test.stooge_sort_partial(PartialPolicy::IncomparableLast)
+            .unwrap();
+        assert_eq!(test, [Set(0b00), Set(0b11)]);
+    }
+}
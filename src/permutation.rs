@@ -0,0 +1,116 @@
+//! Applying permutations to slices.
+
+use alloc::vec;
+
+/// Reorders `v` so that `v[i]` becomes the element previously at `permutation[i]`.
+///
+/// This is how to apply the result of [`stooge_argsort`](crate::StoogeAlloc::stooge_argsort)
+/// and friends. Call it once for every slice that should be reordered the same way.
+/// It takes O(n) time, and allocates O(n) extra space.
+///
+/// # Panics
+///
+/// Panics if `permutation` isn't the same length as `v`, or isn't a permutation of
+/// `0..v.len()` (that is, if any index is out of bounds or appears more than once).
+///
+/// ```
+/// use stoogesort::{apply_permutation, StoogeAlloc};
+/// let mut letters = ['c', 'a', 'b'];
+/// let mut numbers = [3, 1, 2];
+///
+/// let order = letters.stooge_argsort();
+/// assert_eq!(order, [1, 2, 0]);
+///
+/// apply_permutation(&mut letters, &order);
+/// apply_permutation(&mut numbers, &order);
+/// assert_eq!(letters, ['a', 'b', 'c']);
+/// assert_eq!(numbers, [1, 2, 3]);
+/// ```
+pub fn apply_permutation<T>(v: &mut [T], permutation: &[usize]) {
+    assert_eq!(
+        v.len(),
+        permutation.len(),
+        "permutation length doesn't match slice length"
+    );
+
+    let mut seen = vec![false; permutation.len()];
+    for &index in permutation {
+        assert!(
+            index < permutation.len() && !seen[index],
+            "not a permutation: index {} is out of bounds or repeated",
+            index
+        );
+        seen[index] = true;
+    }
+
+    apply_indices(v, &mut permutation.to_vec());
+}
+
+/// Reorders `v` so that `v[i]` becomes the element previously at `indices[i]`.
+///
+/// `indices` must be a permutation of `0..v.len()`; it is clobbered in the process.
+pub(crate) fn apply_indices<T>(v: &mut [T], indices: &mut [usize]) {
+    for i in 0..v.len() {
+        let mut index = indices[i];
+        // Everything before `i` has already been moved; follow the chain of
+        // displaced elements until we find where this one ended up.
+        while index < i {
+            index = indices[index];
+        }
+        indices[i] = index;
+        v.swap(i, index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StoogeAlloc;
+
+    #[test]
+    fn argsort_matches_sort() {
+        crate::test_util::assert_sorts_like_stooge_sort(60, |mut test| {
+            let order = test.stooge_argsort_by_key(|t| t.0);
+            apply_permutation(&mut test, &order);
+            test
+        });
+    }
+
+    #[test]
+    fn argsort_leaves_slice_alone() {
+        let test = [3, 1, 2];
+        assert_eq!(test.stooge_argsort(), [1, 2, 0]);
+        assert_eq!(test, [3, 1, 2]);
+    }
+
+    #[test]
+    fn permutation_is_reusable() {
+        let order = [2, 0, 3, 1];
+        let mut a = ['a', 'b', 'c', 'd'];
+        let mut b = [0, 1, 2, 3];
+
+        apply_permutation(&mut a, &order);
+        apply_permutation(&mut b, &order);
+
+        assert_eq!(a, ['c', 'a', 'd', 'b']);
+        assert_eq!(b, order);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a permutation")]
+    fn repeated_index() {
+        apply_permutation(&mut [1, 2], &[0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a permutation")]
+    fn index_out_of_bounds() {
+        apply_permutation(&mut [1, 2], &[0, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "permutation length")]
+    fn wrong_length() {
+        apply_permutation(&mut [1, 2, 3], &[0, 1]);
+    }
+}
@@ -0,0 +1,135 @@
+//! Sorting part of a slice.
+
+use core::fmt;
+use core::ops::{Bound, Range, RangeBounds};
+
+/// The error returned when asked to sort a range that isn't within the slice.
+///
+/// Returned by [`stooge_sort_range`](crate::Stooge::stooge_sort_range) and friends in
+/// place of the panic that indexing the slice would cause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RangeError {
+    /// The range starts after it ends.
+    StartAfterEnd { start: usize, end: usize },
+    /// The range ends past the end of the slice.
+    EndOutOfBounds { end: usize, len: usize },
+    /// The range's (exclusive) start or (inclusive) end is `usize::MAX`, so it can't be
+    /// converted to a half-open range.
+    Overflow,
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            RangeError::StartAfterEnd { start, end } => {
+                write!(f, "range starts at {} but ends at {}", start, end)
+            }
+            RangeError::EndOutOfBounds { end, len } => write!(
+                f,
+                "range end index {} out of range for slice of length {}",
+                end, len
+            ),
+            RangeError::Overflow => f.write_str("range bound overflows usize"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RangeError {}
+
+/// Converts `range` into a half-open range, checking that it's within a slice of
+/// length `len`.
+pub(crate) fn check<R>(range: R, len: usize) -> Result<Range<usize>, RangeError>
+where
+    R: RangeBounds<usize>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).ok_or(RangeError::Overflow)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).ok_or(RangeError::Overflow)?,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    if start > end {
+        Err(RangeError::StartAfterEnd { start, end })
+    } else if end > len {
+        Err(RangeError::EndOutOfBounds { end, len })
+    } else {
+        Ok(start..end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Stooge;
+    use rand::{distributions::Uniform, Rng};
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn check_bounds() {
+        assert_eq!(check(.., 5), Ok(0..5));
+        assert_eq!(check(1..=3, 5), Ok(1..4));
+        assert_eq!(check(5.., 5), Ok(5..5));
+        assert_eq!(check((Bound::Excluded(1), Bound::Included(2)), 5), Ok(2..3));
+        assert_eq!(
+            check(4..2, 5),
+            Err(RangeError::StartAfterEnd { start: 4, end: 2 })
+        );
+        assert_eq!(
+            check(..6, 5),
+            Err(RangeError::EndOutOfBounds { end: 6, len: 5 })
+        );
+        assert_eq!(check(..=usize::MAX, 5), Err(RangeError::Overflow));
+        assert_eq!(
+            check((Bound::Excluded(usize::MAX), Bound::Unbounded), 5),
+            Err(RangeError::Overflow)
+        );
+    }
+
+    #[test]
+    fn matches_reslicing() {
+        let mut rng = rand::thread_rng();
+        let range = Uniform::new(-100, 100);
+        let mut test: Vec<i64> = (1..=100).map(|_| rng.sample(range)).collect();
+        let mut reference = test.clone();
+
+        test.stooge_sort_range_by_key(20..70, |n| n.abs()).unwrap();
+        reference[20..70].stooge_sort_by_key(|n| n.abs());
+
+        assert_eq!(test, reference);
+    }
+
+    #[test]
+    fn short_ranges() {
+        let mut test = [3, 2, 1];
+        assert_eq!(test.stooge_sort_range(1..1), Ok(()));
+        assert_eq!(test.stooge_sort_range(3..), Ok(()));
+        assert_eq!(test.stooge_sort_range(0..=0), Ok(()));
+        assert_eq!(test, [3, 2, 1]);
+
+        assert_eq!(test.stooge_sort_range(1..), Ok(()));
+        assert_eq!(test, [3, 1, 2]);
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn invalid_range_leaves_slice_alone() {
+        let mut test = [3, 2, 1];
+        assert!(test.stooge_sort_range_by(2..1, |a, b| b.cmp(a)).is_err());
+        assert!(test.stooge_sort_range(..=3).is_err());
+        assert_eq!(test, [3, 2, 1]);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            RangeError::EndOutOfBounds { end: 6, len: 5 }.to_string(),
+            "range end index 6 out of range for slice of length 5"
+        );
+    }
+}
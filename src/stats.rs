@@ -0,0 +1,116 @@
+//! Counting the work done by a sort.
+
+use crate::StoogeObserver;
+
+/// Counts of the work done by a single stooge sort.
+///
+/// Returned by [`stooge_sort_with_stats`](crate::Stooge::stooge_sort_with_stats) and
+/// [`stooge_sort_by_with_stats`](crate::Stooge::stooge_sort_by_with_stats). It's also a
+/// [`StoogeObserver`], so it can be combined with other observers.
+///
+/// Each call sorts a range by comparing (and maybe swapping) its endpoints, then,
+/// if the range has more than 2 elements, sorting its first two thirds, its last two
+/// thirds and its first two thirds again. The sort doesn't actually recurse, but
+/// `calls` and `max_depth` describe the calls the recursive formulation would make.
+///
+/// ```
+/// use stoogesort::Stooge;
+///
+/// // For n ≥ 3, C(n) = 1 + 3 * C(n - n / 3), which grows as
+/// // O(n^(log(3)/log(1.5))).
+/// let mut v: Vec<u32> = (0..27).rev().collect();
+/// let stats = v.stooge_sort_with_stats();
+/// assert_eq!(stats.comparisons, 3280);
+/// assert!((stats.comparisons as f64) < 27f64.powf(3f64.ln() / 1.5f64.ln()));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct StoogeStats {
+    /// The number of times the comparator was called.
+    pub comparisons: u64,
+    /// The number of times two elements were swapped.
+    pub swaps: u64,
+    /// The deepest level of recursion reached, counting the outermost call as 1.
+    ///
+    /// This is 0 if the slice was too short to need sorting.
+    pub max_depth: usize,
+    /// The number of recursive calls made, including the outermost call.
+    ///
+    /// Every call makes exactly one comparison, so this is always equal to
+    /// `comparisons`.
+    pub calls: u64,
+}
+
+impl StoogeObserver for StoogeStats {
+    fn on_enter(&mut self, _left: usize, _right: usize, depth: usize) {
+        self.calls += 1;
+        self.max_depth = self.max_depth.max(depth);
+    }
+    fn on_compare(&mut self, _i: usize, _j: usize, _less: bool) {
+        self.comparisons += 1;
+    }
+    fn on_swap(&mut self, _i: usize, _j: usize) {
+        self.swaps += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Stooge;
+
+    #[test]
+    fn nothing_to_sort() {
+        let mut test: Vec<u8> = [7].to_vec();
+        let stats = test.stooge_sort_with_stats();
+        assert_eq!(stats, Default::default());
+    }
+
+    #[test]
+    fn three_elements() {
+        let mut test = [2, 1, 0];
+        let stats = test.stooge_sort_with_stats();
+        assert_eq!(test, [0, 1, 2]);
+        assert_eq!(stats.calls, 4);
+        assert_eq!(stats.comparisons, 4);
+        assert_eq!(stats.max_depth, 2);
+        // [2, 1, 0] -> [0, 1, 2], then the sorts of [0, 1], [1, 2], [0, 1]
+        // find everything in order.
+        assert_eq!(stats.swaps, 1);
+    }
+
+    #[test]
+    fn equal_elements_always_swap() {
+        let mut test = [0; 10];
+        let stats = test.stooge_sort_with_stats();
+        assert_eq!(stats.swaps, stats.comparisons);
+    }
+
+    #[test]
+    fn sorted_input_never_swaps() {
+        let mut test: Vec<u32> = (0..50).collect();
+        let stats = test.stooge_sort_with_stats();
+        assert_eq!(stats.swaps, 0);
+        assert!(stats.comparisons > 0);
+    }
+
+    #[test]
+    fn comparisons_follow_recurrence() {
+        fn expected(n: u64) -> u64 {
+            if n <= 2 {
+                1
+            } else {
+                1 + 3 * expected(n - n / 3)
+            }
+        }
+
+        for n in 2..=60u64 {
+            let mut test: Vec<u64> = (0..n).rev().collect();
+            let mut counted = 0;
+            let stats = test.stooge_sort_by_with_stats(|a, b| {
+                counted += 1;
+                a.cmp(b)
+            });
+            assert_eq!(stats.comparisons, expected(n));
+            assert_eq!(stats.comparisons, counted);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Sorting one step at a time.
+
+use crate::WorkStack;
+use core::cmp::Ordering;
+
+/// A single step taken by a [`StoogeStepper`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StoogeStep {
+    /// `v[i]` and `v[j]` were compared; `less` is whether `v[i] < v[j]`.
+    ///
+    /// Unless `less` is true, the next step will swap them.
+    Compare { i: usize, j: usize, less: bool },
+    /// `v[i]` and `v[j]` were swapped.
+    Swap { i: usize, j: usize },
+}
+
+/// A stooge sort that can be paused and resumed between steps.
+///
+/// Each call to [`step`](StoogeStepper::step) does exactly one comparison or one swap,
+/// so the sort can be spread across the frames of an animation, for example. Once
+/// [`is_done`](StoogeStepper::is_done), the slice is in the same state
+/// [`stooge_sort_by`](crate::Stooge::stooge_sort_by) would have left it in.
+///
+/// ```
+/// use stoogesort::{StoogeStep, StoogeStepper};
+///
+/// let mut v = [1, 2, 0];
+/// let mut stepper = StoogeStepper::new(&mut v, |a, b| a.cmp(b));
+///
+/// assert_eq!(stepper.step(), Some(StoogeStep::Compare { i: 0, j: 2, less: false }));
+/// assert_eq!(stepper.as_slice(), [1, 2, 0]);
+/// assert_eq!(stepper.step(), Some(StoogeStep::Swap { i: 0, j: 2 }));
+/// assert_eq!(stepper.as_slice(), [0, 2, 1]);
+///
+/// while let Some(step) = stepper.step() {
+///     println!("{:?}", step);
+/// }
+/// assert!(stepper.is_done());
+/// assert_eq!(v, [0, 1, 2]);
+/// ```
+pub struct StoogeStepper<'a, T, F> {
+    v: &'a mut [T],
+    compare: F,
+    work: WorkStack,
+    /// The compare-exchange to do next, looked up ahead of time so that
+    /// [`is_done`](StoogeStepper::is_done) doesn't have to.
+    next: Option<(usize, usize)>,
+    /// A swap decided on by the last comparison, but not yet done.
+    pending_swap: Option<(usize, usize)>,
+}
+
+impl<'a, T, F> StoogeStepper<'a, T, F>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    /// Prepares to sort `v` using stooge sort with a comparator function.
+    ///
+    /// Nothing is compared or moved until the first call to
+    /// [`step`](StoogeStepper::step). The comparator function must define a total
+    /// ordering, as with [`stooge_sort_by`](crate::Stooge::stooge_sort_by).
+    pub fn new(v: &'a mut [T], compare: F) -> Self {
+        let mut work = if v.len() < 2 {
+            WorkStack::empty()
+        } else {
+            WorkStack::new(0, v.len() - 1)
+        };
+        let next = work.next(&mut ());
+
+        StoogeStepper {
+            v,
+            compare,
+            work,
+            next,
+            pending_swap: None,
+        }
+    }
+
+    /// Does the next comparison or swap, returning which it was, or `None` if the
+    /// slice is already sorted.
+    pub fn step(&mut self) -> Option<StoogeStep> {
+        if let Some((i, j)) = self.pending_swap.take() {
+            self.v.swap(i, j);
+            return Some(StoogeStep::Swap { i, j });
+        }
+
+        let (i, j) = self.next?;
+        let less = (self.compare)(&self.v[i], &self.v[j]) == Ordering::Less;
+        if !less {
+            self.pending_swap = Some((i, j));
+        }
+        self.next = self.work.next(&mut ());
+
+        Some(StoogeStep::Compare { i, j, less })
+    }
+
+    /// Returns `true` if there are no steps left, i.e. the slice is sorted.
+    pub fn is_done(&self) -> bool {
+        self.pending_swap.is_none() && self.next.is_none()
+    }
+
+    /// Returns the slice in its current, partially sorted state.
+    pub fn as_slice(&self) -> &[T] {
+        self.v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Stooge;
+
+    #[test]
+    fn nothing_to_sort() {
+        let mut test: [u8; 1] = [42];
+        let mut stepper = StoogeStepper::new(&mut test, u8::cmp);
+        assert!(stepper.is_done());
+        assert_eq!(stepper.step(), None);
+    }
+
+    #[test]
+    fn matches_stooge_sort_by() {
+        crate::test_util::assert_sorts_like_stooge_sort(60, |mut test| {
+            let mut stepper = StoogeStepper::new(&mut test, |a, b| a.0.cmp(&b.0));
+            while !stepper.is_done() {
+                assert!(stepper.step().is_some());
+            }
+            assert_eq!(stepper.step(), None);
+            test
+        });
+    }
+
+    #[test]
+    fn steps_match_stats() {
+        let mut test: Vec<u32> = (0..30).rev().collect();
+        let stats = test.clone().stooge_sort_with_stats();
+
+        let (mut comparisons, mut swaps) = (0, 0);
+        let mut stepper = StoogeStepper::new(&mut test, u32::cmp);
+        while let Some(step) = stepper.step() {
+            match step {
+                StoogeStep::Compare { .. } => comparisons += 1,
+                StoogeStep::Swap { .. } => swaps += 1,
+            }
+        }
+
+        assert_eq!(comparisons, stats.comparisons);
+        assert_eq!(swaps, stats.swaps);
+    }
+
+    #[test]
+    fn swap_follows_failed_comparison() {
+        let mut test = [3, 3, 1, 2];
+        let mut stepper = StoogeStepper::new(&mut test, i32::cmp);
+        let mut last = None;
+        while let Some(step) = stepper.step() {
+            if let StoogeStep::Swap { i, j } = step {
+                assert_eq!(last, Some(StoogeStep::Compare { i, j, less: false }));
+            }
+            last = Some(step);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Helpers shared by the tests of several modules.
+
+use crate::Stooge;
+use rand::{distributions::Uniform, Rng};
+
+/// Returns `len` random `(key, index)` pairs, with lots of repeated keys.
+pub(crate) fn tagged_pairs(len: usize) -> Vec<(u8, usize)> {
+    let mut rng = rand::thread_rng();
+    let range = Uniform::new(0, 8);
+    (0..len).map(|i| (rng.sample(range), i)).collect()
+}
+
+/// Checks that `sort` leaves [`tagged_pairs`] of every length up to `max_len` in
+/// exactly the order [`stooge_sort_by_key`](Stooge::stooge_sort_by_key) does.
+///
+/// `sort` should compare on the key alone, so that any difference in the order of
+/// compare-exchanges shows up in how the equal keys end up.
+pub(crate) fn assert_sorts_like_stooge_sort<F>(max_len: usize, mut sort: F)
+where
+    F: FnMut(Vec<(u8, usize)>) -> Vec<(u8, usize)>,
+{
+    for len in 0..=max_len {
+        let pairs = tagged_pairs(len);
+        let mut expected = pairs.clone();
+        expected.stooge_sort_by_key(|p| p.0);
+        assert_eq!(sort(pairs), expected, "length {}", len);
+    }
+}
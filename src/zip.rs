@@ -0,0 +1,218 @@
+//! Sorting parallel slices together.
+
+use crate::{Stooge, StoogeObserver};
+use core::cmp::Ordering;
+
+/// One or more columns to reorder alongside the keys in [`stooge_sort_zip`].
+///
+/// This is implemented for slices (and [`Vec`](std::vec::Vec)s, with the `alloc`
+/// feature), for mutable references to columns, and for tuples of up to 8 columns, so
+/// any number of columns can be sorted at once by nesting tuples.
+pub trait Columns {
+    /// Returns the number of rows, which must be the same in every column.
+    fn rows(&self) -> usize;
+    /// Swaps rows `a` and `b` in every column.
+    fn swap_rows(&mut self, a: usize, b: usize);
+}
+
+impl<V> Columns for [V] {
+    fn rows(&self) -> usize {
+        self.len()
+    }
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        self.swap(a, b);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<V> Columns for alloc::vec::Vec<V> {
+    fn rows(&self) -> usize {
+        self.len()
+    }
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        self.swap(a, b);
+    }
+}
+
+impl<C: Columns + ?Sized> Columns for &mut C {
+    fn rows(&self) -> usize {
+        (**self).rows()
+    }
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        (**self).swap_rows(a, b);
+    }
+}
+
+macro_rules! impl_columns_for_tuple {
+    ($first:ident $($rest:ident)*) => {
+        impl<$first: Columns, $($rest: Columns),*> Columns for ($first, $($rest,)*) {
+            /// Returns the number of rows in the first column.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the columns have different numbers of rows.
+            fn rows(&self) -> usize {
+                #[allow(non_snake_case)]
+                let ($first, $($rest,)*) = self;
+                let rows = $first.rows();
+                $(assert_eq!($rest.rows(), rows, "columns have different numbers of rows");)*
+                rows
+            }
+            fn swap_rows(&mut self, a: usize, b: usize) {
+                #[allow(non_snake_case)]
+                let ($first, $($rest,)*) = self;
+                $first.swap_rows(a, b);
+                $($rest.swap_rows(a, b);)*
+            }
+        }
+    };
+}
+
+impl_columns_for_tuple!(A);
+impl_columns_for_tuple!(A B);
+impl_columns_for_tuple!(A B C);
+impl_columns_for_tuple!(A B C D);
+impl_columns_for_tuple!(A B C D E);
+impl_columns_for_tuple!(A B C D E F);
+impl_columns_for_tuple!(A B C D E F G);
+impl_columns_for_tuple!(A B C D E F G H);
+
+/// Swaps rows in the columns whenever the sort swaps keys.
+struct SwapRows<'a, C: ?Sized>(&'a mut C);
+
+impl<C: Columns + ?Sized> StoogeObserver for SwapRows<'_, C> {
+    fn on_swap(&mut self, i: usize, j: usize) {
+        self.0.swap_rows(i, j);
+    }
+}
+
+/// Sorts `keys` using stooge sort, making every swap in `columns` as well.
+///
+/// This is for data stored column-wise: the rows stay together without having to zip
+/// the columns into a temporary `Vec<(K, V)>` first. `columns` can be a single slice or
+/// a tuple of them; see [`Columns`].
+///
+/// This sort is unstable, has worst-case
+/// time complexity of O(n^(log(3)/log(1.5)))
+/// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+///
+/// # Panics
+///
+/// Panics if the columns don't have the same number of rows as there are keys.
+///
+/// ```
+/// use stoogesort::stooge_sort_zip;
+/// let mut ids = [3, 1, 2];
+/// let mut names = ["carol", "alice", "bob"];
+/// let mut ages = [35, 30, 25];
+///
+/// stooge_sort_zip(&mut ids, &mut names[..]);
+/// assert_eq!(ids, [1, 2, 3]);
+/// assert_eq!(names, ["alice", "bob", "carol"]);
+///
+/// stooge_sort_zip(&mut ages, &mut (&mut ids[..], &mut names[..]));
+/// assert_eq!(ages, [25, 30, 35]);
+/// assert_eq!(ids, [3, 2, 1]);
+/// assert_eq!(names, ["carol", "bob", "alice"]);
+/// ```
+pub fn stooge_sort_zip<K, C>(keys: &mut [K], columns: &mut C)
+where
+    K: Ord,
+    C: Columns + ?Sized,
+{
+    stooge_sort_zip_by(keys, columns, K::cmp);
+}
+
+/// Sorts `keys` using stooge sort with a comparator function, making every swap in
+/// `columns` as well.
+///
+/// This sort is unstable, has worst-case
+/// time complexity of O(n^(log(3)/log(1.5)))
+/// ≈ O(n^2.7095), and doesn't recurse (see [Recursion](crate#recursion)).
+///
+/// The comparator function must define a total ordering, as with
+/// [`stooge_sort_by`](Stooge::stooge_sort_by).
+///
+/// # Panics
+///
+/// Panics if the columns don't have the same number of rows as there are keys.
+///
+/// ```
+/// use stoogesort::stooge_sort_zip_by;
+/// let mut temperatures = [21.5, 19.0, 23.25];
+/// let mut cities = ["Lisbon", "Oslo", "Rome"];
+///
+/// stooge_sort_zip_by(&mut temperatures, &mut cities[..], |a, b| b.partial_cmp(a).unwrap());
+/// assert_eq!(cities, ["Rome", "Lisbon", "Oslo"]);
+/// ```
+pub fn stooge_sort_zip_by<K, C, F>(keys: &mut [K], columns: &mut C, compare: F)
+where
+    C: Columns + ?Sized,
+    F: FnMut(&K, &K) -> Ordering,
+{
+    assert_eq!(
+        keys.len(),
+        columns.rows(),
+        "columns have a different number of rows than there are keys"
+    );
+    keys.stooge_sort_by_observed(compare, &mut SwapRows(columns));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{distributions::Uniform, Rng};
+
+    #[test]
+    fn rows_stay_together() {
+        let mut rng = rand::thread_rng();
+        let range = Uniform::new(0, 10);
+        let mut keys: Vec<u8> = (0..100).map(|_| rng.sample(range)).collect();
+        let mut ids: Vec<usize> = (0..100).collect();
+        let mut copies = keys.clone();
+        let original = keys.clone();
+
+        stooge_sort_zip(&mut keys, &mut (&mut ids[..], &mut copies[..]));
+
+        assert!(keys.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(keys, copies);
+        for (key, id) in keys.iter().zip(&ids) {
+            assert_eq!(*key, original[*id]);
+        }
+    }
+
+    #[test]
+    fn matches_sorting_pairs() {
+        crate::test_util::assert_sorts_like_stooge_sort(60, |pairs| {
+            let (mut keys, mut values): (Vec<u8>, Vec<usize>) = pairs.into_iter().unzip();
+            stooge_sort_zip(&mut keys, &mut values[..]);
+            keys.into_iter().zip(values).collect()
+        });
+    }
+
+    #[test]
+    fn nested_tuples() {
+        let mut keys = [2, 0, 1];
+        let mut a = ['c', 'a', 'b'];
+        let mut b = [20, 0, 10];
+        let mut c = ["two", "zero", "one"];
+
+        stooge_sort_zip(&mut keys, &mut ((&mut a[..], &mut b[..]), &mut c[..]));
+
+        assert_eq!(a, ['a', 'b', 'c']);
+        assert_eq!(b, [0, 10, 20]);
+        assert_eq!(c, ["zero", "one", "two"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "columns have a different number of rows")]
+    fn too_few_rows() {
+        stooge_sort_zip(&mut [2, 1, 0], &mut [0, 1][..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "columns have different numbers of rows")]
+    fn ragged_columns() {
+        stooge_sort_zip(&mut [2, 1, 0], &mut (&mut [0, 1, 2][..], &mut [0, 1][..]));
+    }
+}
@@ -0,0 +1,206 @@
+//! Tests for the `stoogesort` command-line tool.
+
+#![cfg(feature = "std")]
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output, Stdio};
+
+/// An empty directory of a test's own, removed once the test is done.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(test: &str) -> TempDir {
+        let path = env::temp_dir().join(format!("stoogesort-{}-{}", process::id(), test));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir(&path).unwrap();
+        TempDir(path)
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+
+    fn write(&self, name: &str, contents: &str) {
+        fs::write(self.0.join(name), contents).unwrap();
+    }
+
+    fn read(&self, name: &str) -> String {
+        fs::read_to_string(self.0.join(name)).unwrap()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Runs `stoogesort` in `dir` with `args`, giving it `stdin`.
+fn stoogesort(dir: &TempDir, args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_stoogesort"))
+        .args(args)
+        .current_dir(dir.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // It might not read stdin at all, and exit before this is written.
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+fn stderr(output: &Output) -> &str {
+    std::str::from_utf8(&output.stderr).unwrap()
+}
+
+#[test]
+fn output_can_be_an_input() {
+    let dir = TempDir::new("output_can_be_an_input");
+    dir.write("f", "b\nc\na\n");
+
+    let output = stoogesort(&dir, &["-o", "f", "f"], "");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "");
+    assert_eq!(dir.read("f"), "a\nb\nc\n");
+}
+
+#[test]
+fn sorts_files_together() {
+    let dir = TempDir::new("sorts_files_together");
+    dir.write("a", "3\n1\n");
+    dir.write("b", "4\n2\n");
+
+    let output = stoogesort(&dir, &["a", "b"], "");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "1\n2\n3\n4\n");
+}
+
+#[test]
+fn dash_is_stdin() {
+    let dir = TempDir::new("dash_is_stdin");
+    dir.write("a", "c\na\n");
+    dir.write("b", "e\n");
+
+    let output = stoogesort(&dir, &["a", "-", "b"], "d\nb\n");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "a\nb\nc\nd\ne\n");
+}
+
+#[test]
+fn output_in_missing_directory() {
+    let dir = TempDir::new("output_in_missing_directory");
+
+    let output = stoogesort(&dir, &["-o", "missing/out"], "b\na\n");
+    assert_eq!(output.status.code(), Some(74));
+    assert!(stderr(&output).starts_with("stoogesort: missing/out: "));
+    // Not even a leftover temporary file.
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[cfg(unix)]
+#[test]
+fn dangling_symlink_output() {
+    use std::os::unix::fs::symlink;
+
+    let dir = TempDir::new("dangling_symlink_output");
+    symlink("real", dir.path().join("link")).unwrap();
+
+    let output = stoogesort(&dir, &["-o", "link"], "b\na\n");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(dir.read("real"), "a\nb\n");
+    let link = fs::symlink_metadata(dir.path().join("link")).unwrap();
+    assert!(link.file_type().is_symlink());
+}
+
+#[test]
+fn bad_option() {
+    let dir = TempDir::new("bad_option");
+
+    let output = stoogesort(&dir, &["--frobnicate"], "");
+    assert_eq!(output.status.code(), Some(64));
+    assert_eq!(
+        stderr(&output),
+        "stoogesort: unrecognized option '--frobnicate'\n\
+         Try 'stoogesort --help' for more information.\n"
+    );
+}
+
+#[test]
+fn unparseable_line() {
+    let dir = TempDir::new("unparseable_line");
+    dir.write("a", "2\n1\n");
+    dir.write("b", "3\nthree\n");
+
+    let output = stoogesort(&dir, &["-n", "a", "b"], "");
+    assert_eq!(output.status.code(), Some(65));
+    assert_eq!(stdout(&output), "");
+    assert_eq!(
+        stderr(&output),
+        "stoogesort: b: line 2: invalid integer \"three\"\n"
+    );
+}
+
+#[test]
+fn unparseable_line_on_stdin() {
+    let dir = TempDir::new("unparseable_line_on_stdin");
+
+    let output = stoogesort(&dir, &["-g"], "1.5\nNaN\none\n");
+    assert_eq!(output.status.code(), Some(65));
+    assert_eq!(
+        stderr(&output),
+        "stoogesort: line 3: invalid number \"one\"\n"
+    );
+}
+
+#[test]
+fn skip_invalid() {
+    let dir = TempDir::new("skip_invalid");
+    dir.write("a", "2\none\n1\n");
+    dir.write("b", "3\nthree\n");
+
+    let output = stoogesort(&dir, &["-n", "--skip-invalid", "a", "b"], "");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "1\n2\n3\n");
+    assert_eq!(
+        stderr(&output),
+        "stoogesort: a: line 2: invalid integer \"one\"\n\
+         stoogesort: b: line 2: invalid integer \"three\"\n"
+    );
+}
+
+#[test]
+fn missing_input() {
+    let dir = TempDir::new("missing_input");
+
+    let output = stoogesort(&dir, &["missing"], "");
+    assert_eq!(output.status.code(), Some(74));
+    assert_eq!(stdout(&output), "");
+    assert!(stderr(&output).starts_with("stoogesort: missing: "));
+}
+
+#[test]
+fn closed_stdout() {
+    let dir = TempDir::new("closed_stdout");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_stoogesort"))
+        .current_dir(dir.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Close stdout before it has a chance to write anything, like `head` would.
+    drop(child.stdout.take());
+    child.stdin.take().unwrap().write_all(b"b\na\n").unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stderr(&output), "");
+}